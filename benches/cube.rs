@@ -0,0 +1,172 @@
+//! Benchmarks of turning cubes, generating scrambles and solving, run with `cargo bench`. Pass a
+//! substring to run only matching benchmarks, e.g. `cargo bench -- two_phase`.
+//!
+//! Every input comes from a fixed seed, so runs on the same machine measure the same work.
+
+use scrambler::{random, scramble, Cube, State, Turn};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WARM_UP: Duration = Duration::from_millis(100);
+const MEASUREMENT: Duration = Duration::from_millis(400);
+const SAMPLES: u32 = 20;
+
+// Hand-rolled, not criterion: its dependencies need a newer toolchain than the pinned 1.72.
+/// Times a routine in samples of many iterations, in the manner of criterion, and prints the
+/// fastest, median and slowest time per iteration.
+struct Bencher {
+    filter: Option<String>,
+}
+
+impl Bencher {
+    fn from_args() -> Self {
+        // Cargo passes `--bench`; anything else not starting with a dash is a filter.
+        let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+        Bencher { filter }
+    }
+
+    fn bench<O>(&self, name: &str, mut routine: impl FnMut() -> O) {
+        self.bench_batched(name, || (), |()| routine());
+    }
+
+    /// Only `routine` is timed; `setup` makes a fresh input for every iteration beforehand.
+    fn bench_batched<I, O>(
+        &self,
+        name: &str,
+        mut setup: impl FnMut() -> I,
+        mut routine: impl FnMut(I) -> O,
+    ) {
+        self.measure(name, 1, |iterations| {
+            let inputs: Vec<I> = (0..iterations).map(|_| setup()).collect();
+            let start = Instant::now();
+            for input in inputs {
+                black_box(routine(black_box(input)));
+            }
+            start.elapsed()
+        });
+    }
+
+    /// Runs `routine` on a copy of each of `inputs` per iteration, and prints the mean time per
+    /// input.
+    fn bench_mean<I: Clone, O>(&self, name: &str, inputs: &[I], mut routine: impl FnMut(I) -> O) {
+        self.measure(name, inputs.len() as u64, |iterations| {
+            let inputs: Vec<I> = (0..iterations).flat_map(|_| inputs.iter().cloned()).collect();
+            let start = Instant::now();
+            for input in inputs {
+                black_box(routine(black_box(input)));
+            }
+            start.elapsed()
+        });
+    }
+
+    /// `sample` runs the given number of iterations, each making `runs` runs, and returns how long
+    /// they took.
+    fn measure(&self, name: &str, runs: u64, mut sample: impl FnMut(u64) -> Duration) {
+        if self.filter.as_ref().map_or(false, |filter| !name.contains(filter.as_str())) {
+            return;
+        }
+
+        let mut iterations = 1;
+        let mut warm_up = Duration::ZERO;
+        let mut per_iteration = Duration::ZERO;
+        while warm_up < WARM_UP {
+            let elapsed = sample(iterations);
+            warm_up += elapsed;
+            per_iteration = elapsed / iterations as u32;
+            iterations *= 2;
+        }
+
+        let target = MEASUREMENT / SAMPLES;
+        let iterations = (target.as_nanos() / per_iteration.as_nanos().max(1)).max(1) as u64;
+        let mut times: Vec<f64> = (0..SAMPLES)
+            .map(|_| sample(iterations).as_nanos() as f64 / (iterations * runs) as f64)
+            .collect();
+        times.sort_by(f64::total_cmp);
+
+        println!(
+            "{:<32} time: [{} {} {}]",
+            name,
+            format_time(times[0]),
+            format_time(times[times.len() / 2]),
+            format_time(times[times.len() - 1])
+        );
+    }
+}
+
+fn format_time(nanos: f64) -> String {
+    match nanos {
+        n if n < 1e3 => format!("{:.2} ns", n),
+        n if n < 1e6 => format!("{:.2} µs", n / 1e3),
+        n if n < 1e9 => format!("{:.2} ms", n / 1e6),
+        n => format!("{:.2} s", n / 1e9),
+    }
+}
+
+fn scrambled(seed: u64) -> Cube {
+    let turns = scramble::random_turns(&mut random::seeded(seed), 25);
+    turns.into_iter().fold(Cube::new(), |cube, turn| cube.mv(turn))
+}
+
+fn turning(b: &Bencher) {
+    let cube = scrambled(1);
+    for turn in Turn::ALL {
+        b.bench(&format!("mv/{}", turn), || black_box(&cube).mv(turn));
+    }
+
+    let state = *cube.state();
+    for turn in Turn::ALL {
+        b.bench(&format!("state_turn/{}", turn), || {
+            let mut state = black_box(state);
+            state.turn(turn);
+            state
+        });
+    }
+}
+
+fn generating(b: &Bencher) {
+    for n_turns in [10, 25, 100, 1000] {
+        b.bench_batched(
+            &format!("random_turns/{}", n_turns),
+            || random::seeded(2),
+            |mut rng| scramble::random_turns(&mut rng, n_turns),
+        );
+    }
+}
+
+fn comparing(b: &Bencher) {
+    let (cube, other) = (scrambled(3), scrambled(4));
+    b.bench("facelets/eq", || black_box(&cube).facelets() == black_box(&other).facelets());
+    b.bench("facelets/is_solved", || black_box(&cube).is_solved());
+    b.bench("facelets/to_string", || black_box(&cube).to_facelet_string());
+
+    let facelets = cube.to_facelet_string();
+    b.bench("facelets/parse", || Cube::from_facelet_string(black_box(&facelets)));
+
+    let (state, other) = (*cube.state(), *other.state());
+    b.bench("state/eq", || black_box(state) == black_box(other));
+    b.bench("state/misplaced_tiles", || black_box(state).misplaced_tiles());
+    b.bench("state/solved", || black_box(State::SOLVED).is_solved());
+}
+
+fn solving(b: &Bencher) {
+    for n_turns in [4, 6] {
+        b.bench_batched(
+            &format!("ida/{}", n_turns),
+            || random::seeded(5),
+            |mut rng| scramble::ida_turns(&mut rng, n_turns).unwrap(),
+        );
+    }
+
+    // The tables are generated once per process; leave that out of the timings.
+    scramble::two_phase_turns(&mut random::seeded(6));
+    let rngs: Vec<_> = (6..14).map(random::seeded).collect();
+    b.bench_mean("two_phase/mean_of_8", &rngs, |mut rng| scramble::two_phase_turns(&mut rng));
+}
+
+fn main() {
+    let b = Bencher::from_args();
+    turning(&b);
+    generating(&b);
+    comparing(&b);
+    solving(&b);
+}
@@ -24,7 +24,7 @@
     in
     {
       packages = forAllSystems (pset: with pset;
-        let src = nixpkgs.lib.sourceByRegex self [ "src(/.*)?" "Cargo\\.(toml|lock)" ];
+        let src = nixpkgs.lib.sourceByRegex self [ "src(/.*)?" "benches(/.*)?" "Cargo\\.(toml|lock)" ];
         in
         {
           default = (pkgs.makeRustPlatform {
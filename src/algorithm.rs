@@ -0,0 +1,274 @@
+//! Sequences of turns and transformations on them.
+
+use crate::metric::Metric;
+use crate::notation::{self, ParseError};
+use crate::turn::{Turn, TurnType};
+use std::mem::discriminant;
+use std::str::FromStr;
+
+/// A sequence of turns, parsed from and displayed in WCA notation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Algorithm {
+    turns: Vec<Turn>,
+}
+
+impl Algorithm {
+    /// The turns in the order they are made.
+    pub fn new(turns: Vec<Turn>) -> Self {
+        Algorithm { turns }
+    }
+
+    /// The turns in the order they are made.
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    /// Number of turns, counting each turn as one whatever it moves.
+    pub fn len(&self) -> usize {
+        self.turns.len()
+    }
+
+    /// Whether there are no turns at all.
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    /// Length counted in `metric`.
+    pub fn length(&self, metric: Metric) -> usize {
+        metric.length(&self.turns)
+    }
+
+    /// The sequence that undoes this one: the inverse turns in reverse order.
+    pub fn inverse(&self) -> Algorithm {
+        Algorithm::new(self.turns.iter().rev().map(Turn::inverse).collect())
+    }
+
+    /// Mirrors every turn through the `M` plane, e.g. to turn a right-hand alg into a left-hand
+    /// one.
+    pub fn mirror_lr(&self) -> Algorithm {
+        Algorithm::new(self.turns.iter().map(Turn::mirror_lr).collect())
+    }
+
+    /// Mirrors every turn through the `S` plane.
+    pub fn mirror_fb(&self) -> Algorithm {
+        Algorithm::new(self.turns.iter().map(Turn::mirror_fb).collect())
+    }
+
+    /// Writes the sequence as a commutator `[A, B]` or conjugate `[A: B]` when it is one, or as
+    /// plain turns otherwise. The middle of a conjugate is written as a commutator when it is one,
+    /// so `U R D R' D' U'` becomes `[U: [R, D]]`.
+    pub fn to_notation(&self) -> String {
+        if let Some((a, b)) = self.as_commutator() {
+            return format!("[{}, {}]", a, b);
+        }
+
+        match self.as_conjugate() {
+            Some((a, b)) => match b.as_commutator() {
+                Some((c, d)) => format!("[{}: [{}, {}]]", a, c, d),
+                None => format!("[{}: {}]", a, b),
+            },
+            None => self.to_string(),
+        }
+    }
+
+    /// Splits `A B A' B'` into `A` and `B`, preferring the shortest `A`.
+    fn as_commutator(&self) -> Option<(Algorithm, Algorithm)> {
+        let n = self.turns.len();
+        if n % 2 != 0 {
+            return None;
+        }
+
+        (1..n / 2).find_map(|a| {
+            let (x, rest) = self.turns.split_at(a);
+            let (y, rest) = rest.split_at(n / 2 - a);
+            let (x_inv, y_inv) = rest.split_at(a);
+
+            (undoes(x, x_inv) && undoes(y, y_inv))
+                .then(|| (Algorithm::new(x.to_vec()), Algorithm::new(y.to_vec())))
+        })
+    }
+
+    /// Splits `A B A'` into `A` and `B`, preferring the shortest `A`.
+    fn as_conjugate(&self) -> Option<(Algorithm, Algorithm)> {
+        let n = self.turns.len();
+
+        (1..(n + 1) / 2).find_map(|a| {
+            let (x, rest) = self.turns.split_at(a);
+            let (y, x_inv) = rest.split_at(n - 2 * a);
+
+            undoes(x, x_inv).then(|| (Algorithm::new(x.to_vec()), Algorithm::new(y.to_vec())))
+        })
+    }
+
+    /// Merges turns of the same layer and drops those that cancel, e.g. `R R` becomes `R2` and
+    /// `R R'` disappears. Turns around the same axis commute, so `U D U` becomes `U2 D`, and
+    /// cancellations can cascade: `R U U' R'` simplifies to nothing.
+    pub fn simplify(&self) -> Algorithm {
+        let mut simplified: Vec<Turn> = vec![];
+        for &turn in &self.turns {
+            let same_layer = simplified
+                .iter()
+                .rev()
+                .take_while(|t| t.same_axis(&turn))
+                .position(|t| discriminant(t) == discriminant(&turn))
+                .map(|from_end| simplified.len() - 1 - from_end);
+
+            match same_layer {
+                Some(i) => {
+                    let quarters =
+                        simplified[i].turn_type().quarters() + turn.turn_type().quarters();
+                    match TurnType::from_quarters(quarters) {
+                        Some(turn_type) => simplified[i] = turn.with_turn_type(turn_type),
+                        None => {
+                            simplified.remove(i);
+                        },
+                    }
+                },
+                None => simplified.push(turn),
+            }
+        }
+
+        Algorithm::new(simplified)
+    }
+}
+
+/// Whether `inverse` is exactly `turns` undone, turn by turn.
+fn undoes(turns: &[Turn], inverse: &[Turn]) -> bool {
+    turns.len() == inverse.len()
+        && turns.iter().rev().map(Turn::inverse).eq(inverse.iter().copied())
+}
+
+impl From<Vec<Turn>> for Algorithm {
+    fn from(turns: Vec<Turn>) -> Self {
+        Algorithm::new(turns)
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        notation::parse_turns(s).map(Algorithm::new)
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, turn) in self.turns.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", turn)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Cube;
+
+    fn simplify(s: &str) -> String {
+        s.parse::<Algorithm>().unwrap().simplify().to_string()
+    }
+
+    #[test]
+    fn merges_same_layer() {
+        assert_eq!(simplify("R R"), "R2");
+        assert_eq!(simplify("U U U"), "U'");
+        assert_eq!(simplify("M2 M'"), "M");
+        assert_eq!(simplify("R Rw"), "R Rw");
+    }
+
+    #[test]
+    fn cancels_inverse_turns() {
+        assert_eq!(simplify("R R'"), "");
+        assert_eq!(simplify("F2 F2 U"), "U");
+        assert_eq!(simplify("R U U' R'"), "");
+    }
+
+    #[test]
+    fn commutes_turns_on_same_axis() {
+        assert_eq!(simplify("U D U"), "U2 D");
+        assert_eq!(simplify("R L R' L'"), "");
+        assert_eq!(simplify("U E y' D U'"), "E y' D");
+        assert_eq!(simplify("R U R"), "R U R");
+    }
+
+    #[test]
+    fn inverse_undoes_algorithm() {
+        let alg: Algorithm = "R U2 Fw' M x D' S2 y".parse().unwrap();
+        let cube =
+            alg.turns().iter().chain(alg.inverse().turns()).fold(Cube::new(), |c, &t| c.mv(t));
+
+        assert_eq!(alg.inverse().to_string(), "y' S2 D x' M' Fw U2 R'");
+        assert!(cube.is_solved());
+        assert_eq!(alg.inverse().inverse(), alg);
+    }
+
+    #[test]
+    fn mirrors_algorithms() {
+        let sune: Algorithm = "R U R' U R U2 R'".parse().unwrap();
+        assert_eq!(sune.mirror_lr().to_string(), "L' U' L U' L' U2 L");
+        assert_eq!(sune.mirror_fb().to_string(), "R' U' R U' R' U2 R");
+
+        let alg: Algorithm = "Rw M' x F Bw' S z E".parse().unwrap();
+        assert_eq!(alg.mirror_lr().to_string(), "Lw' M' x F' Bw S' z' E'");
+        assert_eq!(alg.mirror_fb().to_string(), "Rw' M x' B' Fw S z E'");
+        assert_eq!(alg.mirror_lr().mirror_lr(), alg);
+        assert_eq!(alg.mirror_fb().mirror_fb(), alg);
+    }
+
+    #[test]
+    fn mirrored_algorithm_keeps_its_effect() {
+        // The T permutation swaps two corners and two edges, so it and its mirrors have order two.
+        let alg: Algorithm = "R U R' U' R' F R2 U' R' U' R U R' F'".parse().unwrap();
+        for mirrored in [alg.mirror_lr(), alg.mirror_fb()] {
+            let twice = mirrored.turns().iter().chain(mirrored.turns());
+            assert!(twice.fold(Cube::new(), |c, &t| c.mv(t)).is_solved());
+            assert!(!mirrored.turns().iter().fold(Cube::new(), |c, &t| c.mv(t)).is_solved());
+        }
+    }
+
+    #[test]
+    fn detects_commutators_and_conjugates() {
+        let notation = |s: &str| s.parse::<Algorithm>().unwrap().to_notation();
+
+        assert_eq!(notation("R U R' U'"), "[R, U]");
+        assert_eq!(notation("[R U R', D]"), "[R U R', D]");
+        assert_eq!(notation("[U: R U R']"), "[U: R U R']");
+        assert_eq!(notation("[U: [R, D]]"), "[U: [R, D]]");
+        assert_eq!(notation("[F R: U]"), "[F: R U R']");
+        assert_eq!(notation("R U R' U"), "R U R' U");
+        assert_eq!(notation("R R'"), "R R'");
+        assert_eq!(notation(""), "");
+    }
+
+    #[test]
+    fn brackets_keep_cube_state() {
+        let apply = |s: &str| {
+            let alg: Algorithm = s.parse().unwrap();
+            alg.turns().iter().fold(Cube::new(), |c, &t| c.mv(t)).facelets()
+        };
+
+        assert_eq!(apply("[R U R', D]"), apply("R U R' D R U' R' D'"));
+        assert_eq!(apply("[M: U2]"), apply("M U2 M'"));
+        assert_eq!(apply("(R U R' U')6"), Cube::new().facelets());
+        assert_eq!(apply("[[R, U], [F, D]]"), apply("[R, U] [F, D] [U, R] [D, F]"));
+        assert_eq!(apply("[x: [R, U]]"), apply("[R, F]"));
+    }
+
+    #[test]
+    fn keeps_cube_state() {
+        let alg: Algorithm = "R L R U D' U' F F B2 F2 M x M' x' R'".parse().unwrap();
+        let simplified = alg.simplify();
+        let apply = |alg: &Algorithm| alg.turns().iter().fold(Cube::new(), |c, &t| c.mv(t));
+
+        assert!(simplified.len() < alg.len());
+        assert!(simplified.length(Metric::Htm) < alg.length(Metric::Htm));
+        assert_eq!(apply(&simplified).facelets(), apply(&alg).facelets());
+    }
+}
@@ -1,363 +1,412 @@
+//! The cube as the colors of its 54 stickers, along with the moves that scrambled it.
+
+use crate::cubie::CubieCube;
+use crate::metric::Metric;
+use crate::notation::ParseError;
+use crate::orientation::Orientation;
+use crate::render::TextRenderer;
+use crate::state::State;
 use crate::turn::{Turn, TurnType};
-use colored::Colorize;
-use std::mem::discriminant;
+use rand::Rng;
+use std::io::{self, Write};
 
+pub use crate::cubie::InvalidState;
+
+/// How a [`Cube`] is serialized: its move history and a facelet string of its state.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedCube {
+    moves: Vec<Turn>,
+    facelets: String,
+}
+
+#[cfg(feature = "serde")]
+impl From<Cube> for SerializedCube {
+    fn from(cube: Cube) -> Self {
+        SerializedCube { facelets: cube.to_facelet_string(), moves: cube.moves }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedCube> for Cube {
+    type Error = ParseError;
+
+    fn try_from(serialized: SerializedCube) -> Result<Self, Self::Error> {
+        let mut cube = Cube::from_facelet_string(&serialized.facelets)?;
+        cube.moves = serialized.moves;
+
+        Ok(cube)
+    }
+}
+
+const EXPECTED_FACELET: &str = "one of 54 facelets (U R F D L B)";
+
+/// Faces are named by position; a solved cube in the standard orientation has white up and green
+/// in front.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "SerializedCube", try_from = "SerializedCube")
+)]
 pub struct Cube {
     moves: Vec<Turn>,
-    green: Face,
-    red: Face,
-    blue: Face,
-    orange: Face,
-    white: Face,
-    yellow: Face,
+    state: State,
 }
 
 impl Cube {
+    /// A solved cube held white up, green front, with no moves made.
     pub fn new() -> Self {
-        Cube {
-            moves: vec![],
-            green: Face::new(Color::Green),
-            red: Face::new(Color::Red),
-            blue: Face::new(Color::Blue),
-            orange: Face::new(Color::Orange),
-            white: Face::new(Color::White),
-            yellow: Face::new(Color::Yellow),
-        }
+        Cube { moves: vec![], state: State::SOLVED }
+    }
+
+    /// A state drawn uniformly from every reachable state, with an empty move history.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        CubieCube::random(rng).to_cube()
     }
 
-    pub fn print(&self) {
-        println!();
+    /// Writes the moves made so far, followed by their length in `metric` if given, and the net.
+    pub fn print<W: Write>(
+        &self,
+        renderer: &mut TextRenderer<W>,
+        metric: Option<Metric>,
+    ) -> io::Result<()> {
+        writeln!(renderer)?;
         for t in &self.moves {
-            print!(" {}", t);
+            write!(renderer, " {}", t)?;
+        }
+        if let Some(metric) = metric {
+            write!(renderer, " ({} {})", metric.length(&self.moves), metric)?;
         }
-        print!("\n\n");
-        for row in &self.white.tiles {
-            print!("        ");
+        write!(renderer, "\n\n")?;
+        for row in self.face(Face::U) {
+            write!(renderer, "        ")?;
             for t in row {
-                print!("{}", t);
+                renderer.write_tile(t)?;
             }
-            println!();
+            writeln!(renderer)?;
         }
-        println!();
+        writeln!(renderer)?;
+        let middle = [Face::L, Face::F, Face::R, Face::B].map(|face| self.face(face));
         for row in 0..3 {
-            print!(" ");
-            for &face in [&self.orange, &self.green, &self.red, &self.blue].iter() {
-                for t in &face.tiles[row] {
-                    print!("{}", t);
+            write!(renderer, " ")?;
+            for face in &middle {
+                for &t in &face[row] {
+                    renderer.write_tile(t)?;
                 }
-                print!(" ");
+                write!(renderer, " ")?;
             }
-            println!();
+            writeln!(renderer)?;
         }
-        println!();
-        for row in &self.yellow.tiles {
-            print!("        ");
+        writeln!(renderer)?;
+        for row in self.face(Face::D) {
+            write!(renderer, "        ")?;
             for t in row {
-                print!("{}", t);
+                renderer.write_tile(t)?;
             }
-            println!();
+            writeln!(renderer)?;
         }
+
+        Ok(())
     }
 
-    fn looped_update(
-        to_update: [&Face; 4],
-        update_sections: [Triplet; 4],
-        turn_type: &TurnType,
-    ) -> Vec<Option<Face>> {
-        to_update
-            .iter()
-            .enumerate()
-            .map(|(i, &face)| {
-                let other_idx = match turn_type {
-                    TurnType::Clock => (i + 1) % 4,
-                    TurnType::Prime => ((((i as isize - 1) % 4) + 4) % 4) as usize,
-                    TurnType::Double => (i + 2) % 4,
-                };
-
-                Some(face.update_triplet(
-                    &update_sections[i],
-                    &update_sections[other_idx],
-                    to_update[other_idx].get_triplet(&update_sections[other_idx]).as_slice(),
-                ))
-            })
-            .collect()
+    /// Every turn made since the cube was created, in order.
+    pub fn moves(&self) -> &[Turn] {
+        &self.moves
     }
 
-    pub fn mv(&self, turn: Turn) -> Self {
-        match turn {
-            Turn::U(turn_type) => {
-                let to_update = [&self.green, &self.red, &self.blue, &self.orange];
-                let update_sections = [Triplet::Top, Triplet::Top, Triplet::Top, Triplet::Top];
-                let mut updated = Cube::looped_update(to_update, update_sections, &turn_type);
-
-                Cube {
-                    moves: self
-                        .moves
-                        .iter()
-                        .chain(vec![Turn::U(turn_type.clone())].iter())
-                        .map(|t| t.clone())
-                        .collect(),
-                    green: updated[0].take().unwrap(),
-                    red: updated[1].take().unwrap(),
-                    blue: updated[2].take().unwrap(),
-                    orange: updated[3].take().unwrap(),
-                    white: self.white.rotate(&turn_type),
-                    yellow: self.yellow.clone(),
-                }
-            },
-            Turn::D(turn_type) => {
-                let to_update = [&self.green, &self.orange, &self.blue, &self.red];
-                let update_sections =
-                    [Triplet::Bottom, Triplet::Bottom, Triplet::Bottom, Triplet::Bottom];
-                let mut updated = Cube::looped_update(to_update, update_sections, &turn_type);
-
-                Cube {
-                    moves: self
-                        .moves
-                        .iter()
-                        .chain(vec![Turn::D(turn_type.clone())].iter())
-                        .map(|t| t.clone())
-                        .collect(),
-                    green: updated[0].take().unwrap(),
-                    red: updated[3].take().unwrap(),
-                    blue: updated[2].take().unwrap(),
-                    orange: updated[1].take().unwrap(),
-                    white: self.white.clone(),
-                    yellow: self.yellow.rotate(&turn_type),
-                }
-            },
-            Turn::R(turn_type) => {
-                let to_update = [&self.green, &self.yellow, &self.blue, &self.white];
-                let update_sections =
-                    [Triplet::Right, Triplet::Right, Triplet::Left, Triplet::Right];
-                let mut updated = Cube::looped_update(to_update, update_sections, &turn_type);
-
-                Cube {
-                    moves: self
-                        .moves
-                        .iter()
-                        .chain(vec![Turn::R(turn_type.clone())].iter())
-                        .map(|t| t.clone())
-                        .collect(),
-                    green: updated[0].take().unwrap(),
-                    red: self.red.rotate(&turn_type),
-                    blue: updated[2].take().unwrap(),
-                    orange: self.orange.clone(),
-                    white: updated[3].take().unwrap(),
-                    yellow: updated[1].take().unwrap(),
-                }
-            },
-            Turn::L(turn_type) => {
-                let to_update = [&self.green, &self.white, &self.blue, &self.yellow];
-                let update_sections = [Triplet::Left, Triplet::Left, Triplet::Right, Triplet::Left];
-                let mut updated = Cube::looped_update(to_update, update_sections, &turn_type);
-
-                Cube {
-                    moves: self
-                        .moves
-                        .iter()
-                        .chain(vec![Turn::L(turn_type.clone())].iter())
-                        .map(|t| t.clone())
-                        .collect(),
-                    green: updated[0].take().unwrap(),
-                    red: self.red.clone(),
-                    blue: updated[2].take().unwrap(),
-                    orange: self.orange.rotate(&turn_type),
-                    white: updated[1].take().unwrap(),
-                    yellow: updated[3].take().unwrap(),
-                }
-            },
-            Turn::F(turn_type) => {
-                let to_update = [&self.white, &self.orange, &self.yellow, &self.red];
-                let update_sections =
-                    [Triplet::Bottom, Triplet::Right, Triplet::Top, Triplet::Left];
-                let mut updated = Cube::looped_update(to_update, update_sections, &turn_type);
-
-                Cube {
-                    moves: self
-                        .moves
-                        .iter()
-                        .chain(vec![Turn::F(turn_type.clone())].iter())
-                        .map(|t| t.clone())
-                        .collect(),
-                    green: self.green.rotate(&turn_type),
-                    red: updated[3].take().unwrap(),
-                    blue: self.blue.clone(),
-                    orange: updated[1].take().unwrap(),
-                    white: updated[0].take().unwrap(),
-                    yellow: updated[2].take().unwrap(),
-                }
-            },
-            Turn::B(turn_type) => {
-                let to_update = [&self.white, &self.red, &self.yellow, &self.orange];
-                let update_sections =
-                    [Triplet::Top, Triplet::Right, Triplet::Bottom, Triplet::Left];
-                let mut updated = Cube::looped_update(to_update, update_sections, &turn_type);
-
-                Cube {
-                    moves: self
-                        .moves
-                        .iter()
-                        .chain(vec![Turn::B(turn_type.clone())].iter())
-                        .map(|t| t.clone())
-                        .collect(),
-                    green: self.green.clone(),
-                    red: updated[1].take().unwrap(),
-                    blue: self.blue.rotate(&turn_type),
-                    orange: updated[3].take().unwrap(),
-                    white: updated[0].take().unwrap(),
-                    yellow: updated[2].take().unwrap(),
-                }
-            },
+    /// The colors of the stickers, without the move history.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Whether every face shows a single color.
+    pub fn is_solved(&self) -> bool {
+        self.state.is_solved()
+    }
+
+    /// Number of tiles whose color differs from the center of the face they sit on.
+    pub fn misplaced_tiles(&self) -> usize {
+        self.state.misplaced_tiles()
+    }
+
+    /// Checks that this state can be reached from a solved cube by turning faces, reporting the
+    /// first problem found otherwise.
+    pub fn validate(&self) -> Result<(), InvalidState> {
+        let facelets = self.facelets();
+        for color in Color::ALL {
+            let count = facelets.iter().filter(|&&c| c == color).count();
+            if count != 9 {
+                return Err(InvalidState::StickerCount { color, count });
+            }
         }
+        self.validate_centers()?;
+
+        CubieCube::from_cube(self)?.validate()
     }
-}
 
-#[derive(Clone)]
-struct Face {
-    tiles: Vec<Vec<Color>>,
-}
+    /// Tiles of the face in position `face`, row by row as laid out by [`Cube::print`].
+    pub fn face(&self, face: Face) -> [[Color; 3]; 3] {
+        let tiles = self.state.face(face);
 
-impl Face {
-    fn new(color: Color) -> Self {
-        Face { tiles: vec![vec![color; 3]; 3] }
+        [0, 1, 2].map(|row| [0, 1, 2].map(|column| tiles[row * 3 + column]))
+    }
+
+    /// Tiles in `URFDLB` face order, each face read row by row as laid out by [`Cube::print`].
+    pub fn facelets(&self) -> [Color; 54] {
+        *self.state.facelets()
+    }
+
+    /// Inverse of [`Cube::facelets`], with an empty move history.
+    pub fn from_facelets(facelets: &[Color; 54]) -> Self {
+        Cube { moves: vec![], state: State::from_facelets(*facelets) }
+    }
+
+    /// The 54 character facelet string used by Kociemba's solver and most other tools, e.g.
+    /// `UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB` when solved. Each tile is named by
+    /// the face its color sits on in a solved cube held white up, green front.
+    pub fn to_facelet_string(&self) -> String {
+        self.facelets().iter().map(Color::face_letter).collect()
     }
 
-    fn get_triplet(&self, section: &Triplet) -> Vec<Color> {
-        match section {
-            Triplet::Top => self.tiles[0].clone(),
-            Triplet::Right => self.tiles.iter().map(|row| row[2].clone()).collect(),
-            Triplet::Bottom => self.tiles[2].clone(),
-            Triplet::Left => self.tiles.iter().map(|row| row[0].clone()).collect(),
+    /// Inverse of [`Cube::to_facelet_string`]. Whitespace is ignored; the state is not validated.
+    pub fn from_facelet_string(s: &str) -> Result<Self, ParseError> {
+        let mut facelets = [Color::White; 54];
+        let mut count = 0;
+        for (position, c) in s.chars().enumerate().filter(|(_, c)| !c.is_whitespace()) {
+            if count == facelets.len() {
+                return Err(ParseError { position, found: Some(c), expected: "end of input" });
+            }
+            facelets[count] = Color::from_face_letter(c).ok_or(ParseError {
+                position,
+                found: Some(c),
+                expected: EXPECTED_FACELET,
+            })?;
+            count += 1;
+        }
+        if count < facelets.len() {
+            return Err(ParseError {
+                position: s.chars().count(),
+                found: None,
+                expected: EXPECTED_FACELET,
+            });
         }
+
+        Ok(Cube::from_facelets(&facelets))
     }
 
-    fn update_triplet(&self, section: &Triplet, other_section: &Triplet, cubies: &[Color]) -> Face {
-        let reverse = match section {
-            Triplet::Top => {
-                discriminant(other_section) == discriminant(&Triplet::Left)
-                    || discriminant(other_section) == discriminant(&Triplet::Bottom)
-            },
-            Triplet::Right => {
-                discriminant(other_section) == discriminant(&Triplet::Left)
-                    || discriminant(other_section) == discriminant(&Triplet::Bottom)
-            },
-            Triplet::Bottom => {
-                discriminant(other_section) == discriminant(&Triplet::Right)
-                    || discriminant(other_section) == discriminant(&Triplet::Top)
-            },
-            Triplet::Left => {
-                discriminant(other_section) == discriminant(&Triplet::Right)
-                    || discriminant(other_section) == discriminant(&Triplet::Top)
-            },
-        };
+    /// The same state turned over in the hand to be held in `orientation`. The rotations are not
+    /// recorded as moves, so moves made afterwards turn the faces as now held. The centers must be
+    /// those of a real cube, with every color opposite its usual partner.
+    pub fn reoriented(&self, orientation: Orientation) -> Result<Self, InvalidState> {
+        for rotations in holds() {
+            let mut state = self.state;
+            for turn in rotations.into_iter().flatten() {
+                state.turn(turn);
+            }
+            if state.face(Face::U)[4] == orientation.up()
+                && state.face(Face::F)[4] == orientation.front()
+            {
+                return Ok(Cube { moves: self.moves.clone(), state });
+            }
+        }
 
-        let prepared_cubies: Vec<Color> = if reverse {
-            cubies.into_iter().rev().map(|c| c.clone()).collect()
-        } else {
-            cubies.to_vec()
+        Err(self.validate_centers().err().unwrap_or(InvalidState::MisplacedCenters))
+    }
+
+    /// Checks that the centers are those of a solved cube held some way: no color twice, every
+    /// color opposite its usual partner and the colors going round the same way.
+    fn validate_centers(&self) -> Result<(), InvalidState> {
+        let centers = Face::ALL.map(|face| self.state.face(face)[4]);
+        if let Some(f) = (1..6).find(|&f| centers[..f].contains(&centers[f])) {
+            return Err(InvalidState::DuplicateCenter(centers[f]));
+        }
+        if Face::ALL
+            .iter()
+            .any(|&f| centers[f.opposite() as usize] != centers[f as usize].opposite())
+        {
+            return Err(InvalidState::MisplacedCenters);
+        }
+
+        let held = |rotations: [Option<Turn>; 2]| {
+            let mut state = State::SOLVED;
+            for turn in rotations.into_iter().flatten() {
+                state.turn(turn);
+            }
+            Face::ALL.map(|face| state.face(face)[4])
         };
+        if !holds().any(|rotations| held(rotations) == centers) {
+            return Err(InvalidState::MirroredCenters);
+        }
 
-        Face {
-            tiles: {
-                match section {
-                    Triplet::Top => {
-                        vec![prepared_cubies, self.tiles[1].clone(), self.tiles[2].clone()]
-                    },
-                    Triplet::Right => {
-                        let mut tile_array = self.tiles.clone();
-                        for i in 0..3 {
-                            tile_array[i][2] = prepared_cubies[i].clone();
-                        }
-
-                        tile_array
-                    },
-                    Triplet::Bottom => {
-                        vec![self.tiles[0].clone(), self.tiles[1].clone(), prepared_cubies]
-                    },
-                    Triplet::Left => {
-                        let mut tile_array = self.tiles.clone();
-                        for i in 0..3 {
-                            tile_array[i][0] = prepared_cubies[i].clone();
-                        }
-
-                        tile_array
-                    },
-                }
-            },
+        Ok(())
+    }
+
+    /// A copy of the cube with `turn` made and recorded.
+    pub fn mv(&self, turn: Turn) -> Self {
+        let mut cube = self.clone();
+        cube.turn(turn);
+
+        cube
+    }
+
+    /// Makes `turn` in place and records it.
+    pub fn turn(&mut self, turn: Turn) {
+        self.state.turn(turn);
+        self.moves.push(turn);
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Cube::new()
+    }
+}
+
+/// The 24 ways of holding a cube, as rotations from white up, green front: a tilt bringing some
+/// face up, then a spin about the vertical axis.
+fn holds() -> impl Iterator<Item = [Option<Turn>; 2]> {
+    let quarters = [None, Some(TurnType::Clock), Some(TurnType::Double), Some(TurnType::Prime)];
+    let tilts = quarters
+        .map(|tt| tt.map(Turn::X))
+        .into_iter()
+        .chain([Some(Turn::Z(TurnType::Clock)), Some(Turn::Z(TurnType::Prime))]);
+
+    tilts.flat_map(move |tilt| quarters.map(|tt| [tilt, tt.map(Turn::Y)]))
+}
+
+/// A position on the cube, named by the letter of its turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Face {
+    /// Up.
+    U,
+    /// Right.
+    R,
+    /// Front.
+    F,
+    /// Down.
+    D,
+    /// Left.
+    L,
+    /// Back.
+    B,
+}
+
+impl Face {
+    /// In the `URFDLB` order of facelet strings.
+    pub const ALL: [Face; 6] = [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B];
+
+    /// The letter naming the face in turns and facelet strings.
+    pub fn letter(&self) -> char {
+        match self {
+            Face::U => 'U',
+            Face::R => 'R',
+            Face::F => 'F',
+            Face::D => 'D',
+            Face::L => 'L',
+            Face::B => 'B',
         }
     }
 
-    fn rotate(&self, turn_type: &TurnType) -> Face {
-        Face {
-            tiles: {
-                let mut tile_array = vec![vec![self.tiles[1][1].clone(); 3]; 3];
-                match turn_type {
-                    TurnType::Clock => {
-                        // 00 01 02    20 10 00
-                        // 10 11 12 -> 21 11 01
-                        // 20 21 22    22 12 02
-                        for j in 0..3 {
-                            for i in (0..3).rev() {
-                                tile_array[j][2 - i] = self.tiles[i][j].clone();
-                            }
-                        }
-                    },
-                    TurnType::Prime => {
-                        // 00 01 02    02 12 22
-                        // 10 11 12 -> 01 11 21
-                        // 20 21 22    00 10 20
-                        for j in (0..3).rev() {
-                            for i in 0..3 {
-                                tile_array[2 - j][i] = self.tiles[i][j].clone();
-                            }
-                        }
-                    },
-                    TurnType::Double => {
-                        // 00 01 02    22 21 20
-                        // 10 11 12 -> 12 11 10
-                        // 20 21 22    02 01 00
-                        for i in (0..3).rev() {
-                            for j in (0..3).rev() {
-                                tile_array[2 - i][2 - j] = self.tiles[i][j].clone();
-                            }
-                        }
-                    },
-                }
+    /// The face named by `letter`, which must be upper case.
+    pub fn from_letter(letter: char) -> Option<Face> {
+        Face::ALL.into_iter().find(|f| f.letter() == letter)
+    }
 
-                tile_array
-            },
+    /// The face on the other side of the cube.
+    pub fn opposite(&self) -> Face {
+        match self {
+            Face::U => Face::D,
+            Face::R => Face::L,
+            Face::F => Face::B,
+            Face::D => Face::U,
+            Face::L => Face::R,
+            Face::B => Face::F,
         }
     }
-}
 
-#[derive(Clone)]
-enum Triplet {
-    Top,
-    Right,
-    Bottom,
-    Left,
+    /// The color of this face on a solved cube held white up, green front.
+    pub fn solved_color(&self) -> Color {
+        match self {
+            Face::U => Color::White,
+            Face::R => Color::Red,
+            Face::F => Color::Green,
+            Face::D => Color::Yellow,
+            Face::L => Color::Orange,
+            Face::B => Color::Blue,
+        }
+    }
 }
 
-#[derive(Clone, PartialEq)]
-enum Color {
+/// A sticker, named after its color in the western scheme. Other schemes only repaint stickers; see
+/// [`Scheme`](crate::render::Scheme).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    /// Front on a solved cube.
     Green,
+    /// Right on a solved cube.
     Red,
+    /// Back on a solved cube.
     Blue,
+    /// Left on a solved cube.
     Orange,
+    /// Up on a solved cube.
     White,
+    /// Down on a solved cube.
     Yellow,
 }
 
-impl std::fmt::Display for Color {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Color {
+    /// Every color, in the order they are declared.
+    pub const ALL: [Color; 6] =
+        [Color::Green, Color::Red, Color::Blue, Color::Orange, Color::White, Color::Yellow];
+
+    /// The face showing this color on a solved cube held white up, green front.
+    pub fn home(&self) -> Face {
+        Face::ALL.into_iter().find(|f| f.solved_color() == *self).unwrap()
+    }
+
+    /// Letter of the [home](Color::home) face, as used in facelet strings.
+    pub fn face_letter(&self) -> char {
+        self.home().letter()
+    }
+
+    /// Initial of the color's name.
+    pub fn letter(&self) -> char {
+        match self {
+            Color::Green => 'G',
+            Color::Red => 'R',
+            Color::Blue => 'B',
+            Color::Orange => 'O',
+            Color::White => 'W',
+            Color::Yellow => 'Y',
+        }
+    }
+
+    /// The color whose [home](Color::home) face `letter` names, as read from facelet strings.
+    pub fn from_face_letter(letter: char) -> Option<Color> {
+        Face::from_letter(letter).map(|f| f.solved_color())
+    }
+
+    /// The color with this name, in any case, e.g. `white` or `Red`.
+    pub fn from_name(name: &str) -> Option<Color> {
+        Color::ALL.into_iter().find(|c| name.eq_ignore_ascii_case(&format!("{:?}", c)))
+    }
+
+    /// The color of the opposite face of a solved cube.
+    pub fn opposite(&self) -> Color {
+        self.home().opposite().solved_color()
+    }
+
+    /// Default shade of the sticker.
+    pub fn rgb(&self) -> [u8; 3] {
         match self {
-            Color::Green => write!(f, "{}", "\u{2588}\u{2589}".truecolor(38, 203, 51)),
-            Color::Red => write!(f, "{}", "\u{2588}\u{2589}".truecolor(178, 36, 36)),
-            Color::Blue => write!(f, "{}", "\u{2588}\u{2589}".truecolor(32, 80, 173)),
-            Color::Orange => write!(f, "{}", "\u{2588}\u{2589}".truecolor(255, 102, 0)),
-            Color::White => write!(f, "{}", "\u{2588}\u{2589}".truecolor(239, 239, 239)),
-            Color::Yellow => write!(f, "{}", "\u{2588}\u{2589}".truecolor(255, 251, 0)),
+            Color::Green => [38, 203, 51],
+            Color::Red => [178, 36, 36],
+            Color::Blue => [32, 80, 173],
+            Color::Orange => [255, 102, 0],
+            Color::White => [239, 239, 239],
+            Color::Yellow => [255, 251, 0],
         }
     }
 }
@@ -365,39 +414,253 @@ impl std::fmt::Display for Color {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::{Style, TextMode};
+
+    #[test]
+    fn facelet_string_of_solved_cube() {
+        assert_eq!(
+            Cube::new().to_facelet_string(),
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB"
+        );
+    }
+
+    #[test]
+    fn facelet_string_after_turn() {
+        // Known output of Kociemba's tools for R.
+        assert_eq!(
+            Cube::new().mv(Turn::R(TurnType::Clock)).to_facelet_string(),
+            "UUFUUFUUFRRRRRRRRRFFDFFDFFDDDBDDBDDBLLLLLLLLLUBBUBBUBB"
+        );
+    }
+
+    #[test]
+    fn facelet_string_round_trips() {
+        let cube = [Turn::R(TurnType::Clock), Turn::U(TurnType::Prime), Turn::M(TurnType::Double)]
+            .into_iter()
+            .fold(Cube::new(), |c, t| c.mv(t));
+        let s = cube.to_facelet_string();
+
+        assert_eq!(Cube::from_facelet_string(&s).unwrap().facelets(), cube.facelets());
+        assert_eq!(
+            Cube::from_facelet_string(&format!(" {} ", s.replace('U', "U\n"))).unwrap().facelets(),
+            cube.facelets()
+        );
+    }
+
+    #[test]
+    fn facelet_string_errors() {
+        let solved = Cube::new().to_facelet_string();
+
+        assert_eq!(
+            Cube::from_facelet_string(&solved[..53]).err(),
+            Some(ParseError { position: 53, found: None, expected: EXPECTED_FACELET })
+        );
+        assert_eq!(
+            Cube::from_facelet_string(&format!("{}U", solved)).err(),
+            Some(ParseError { position: 54, found: Some('U'), expected: "end of input" })
+        );
+        assert_eq!(
+            Cube::from_facelet_string(&solved.replacen('R', "W", 1)).err(),
+            Some(ParseError { position: 9, found: Some('W'), expected: EXPECTED_FACELET })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_moves_and_facelets() {
+        let cube = Cube::new().mv(Turn::R(TurnType::Clock));
+        let json = serde_json::to_string(&cube).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"moves":["R"],"facelets":"UUFUUFUUFRRRRRRRRRFFDFFDFFDDDBDDBDDBLLLLLLLLLUBBUBBUBB"}"#
+        );
+        let parsed: Cube = serde_json::from_str(&json).unwrap();
+        assert_eq!((parsed.moves(), parsed.facelets()), (cube.moves(), cube.facelets()));
+    }
+
+    #[test]
+    fn prints_net() {
+        let mut renderer = TextRenderer::new(vec![], TextMode::Letters, &Style::default());
+        Cube::new().mv(Turn::U(TurnType::Clock)).print(&mut renderer, Some(Metric::Qtm)).unwrap();
+
+        assert_eq!(
+            String::from_utf8(renderer.into_inner()).unwrap(),
+            "\n U (1 QTM)\n\n\
+             \x20       W W W \n\
+             \x20       W W W \n\
+             \x20       W W W \n\n\
+             \x20G G G  R R R  B B B  O O O  \n\
+             \x20O O O  G G G  R R R  B B B  \n\
+             \x20O O O  G G G  R R R  B B B  \n\n\
+             \x20       Y Y Y \n\
+             \x20       Y Y Y \n\
+             \x20       Y Y Y \n"
+        );
+    }
+
+    #[test]
+    fn reorients_without_recording_rotations() {
+        let hold = Orientation::new(Color::Yellow, Color::Red).unwrap();
+        let cube = Cube::new().reoriented(hold).unwrap();
+
+        assert!(cube.moves().is_empty());
+        assert_eq!(
+            (cube.face(Face::U)[1][1], cube.face(Face::F)[1][1]),
+            (Color::Yellow, Color::Red)
+        );
+        assert_eq!(cube.face(Face::R)[1][1], Color::Green);
+        // R held yellow up, red front turns the green face.
+        let turned = cube.mv(Turn::R(TurnType::Clock)).reoriented(Orientation::default()).unwrap();
+        assert_eq!(turned.moves(), [Turn::R(TurnType::Clock)]);
+        assert_eq!(turned.facelets(), Cube::new().mv(Turn::F(TurnType::Clock)).facelets());
+        // Swapping the F and D centers puts green opposite white.
+        let mut facelets = Cube::new().facelets();
+        facelets.swap(22, 31);
+        let misplaced = Cube::from_facelets(&facelets);
+        assert_eq!(
+            misplaced.reoriented(Orientation::default()).unwrap_err(),
+            InvalidState::MisplacedCenters
+        );
+    }
+
+    #[test]
+    fn faces_match_facelets() {
+        let cube = Cube::random(&mut crate::random::seeded(7));
+        let facelets = cube.facelets();
+
+        assert!(cube.validate().is_ok() && cube.moves().is_empty());
+        for face in Face::ALL {
+            let tiles: Vec<Color> = cube.face(face).into_iter().flatten().collect();
+            assert_eq!(tiles, facelets[face as usize * 9..][..9]);
+            assert_eq!(face.opposite().opposite(), face);
+            assert_eq!(face.solved_color().home(), face);
+        }
+    }
+
+    #[test]
+    fn validate_scrambled() {
+        let cube = Cube::new().mv(Turn::R(TurnType::Clock)).mv(Turn::F(TurnType::Prime));
+
+        assert_eq!(cube.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_sticker_count() {
+        let mut facelets = Cube::new().facelets();
+        facelets[0] = Color::Red;
+
+        assert_eq!(
+            Cube::from_facelets(&facelets).validate(),
+            Err(InvalidState::StickerCount { color: Color::Red, count: 10 })
+        );
+    }
+
+    #[test]
+    fn validate_centers() {
+        // Red and orange swapped: a mirror image of a solved cube.
+        let mirrored =
+            Cube::from_facelet_string("UUUUUUUUULLLLLLLLLFFFFFFFFFDDDDDDDDDRRRRRRRRRBBBBBBBBB");
+        assert_eq!(mirrored.unwrap().validate(), Err(InvalidState::MirroredCenters));
+
+        // Red and green swapped: red opposite blue.
+        let adjacent =
+            Cube::from_facelet_string("UUUUUUUUUFFFFFFFFFRRRRRRRRRDDDDDDDDDLLLLLLLLLBBBBBBBBB");
+        assert_eq!(adjacent.unwrap().validate(), Err(InvalidState::MisplacedCenters));
+
+        let held = Cube::new().mv(Turn::X(TurnType::Clock)).mv(Turn::Y(TurnType::Prime));
+        assert_eq!(held.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_swapped_stickers() {
+        let mut facelets = Cube::new().facelets();
+        // U1 and L1 both belong to corner ULB.
+        facelets.swap(0, 36);
+        assert_eq!(
+            Cube::from_facelets(&facelets).validate(),
+            Err(InvalidState::UnknownCorner("ULB"))
+        );
+
+        let mut facelets = Cube::new().facelets();
+        // Flip edge UF by swapping U8 and F2.
+        facelets.swap(7, 19);
+        assert_eq!(Cube::from_facelets(&facelets).validate(), Err(InvalidState::EdgeFlip));
+    }
+
+    #[test]
+    fn rotations_keep_cube_solved() {
+        for turn in [Turn::X(TurnType::Clock), Turn::Y(TurnType::Prime), Turn::Z(TurnType::Double)]
+        {
+            let cube = Cube::new().mv(turn);
+
+            assert!(cube.is_solved(), "{}", turn);
+            assert_ne!(cube.facelets(), Cube::new().facelets(), "{}", turn);
+        }
+    }
+
+    #[test]
+    fn rotation_moves_faces() {
+        let cube = Cube::new().mv(Turn::X(TurnType::Clock)).mv(Turn::Y(TurnType::Clock));
+
+        assert_eq!(cube.face(Face::U)[1][1], Color::Green);
+        assert_eq!(cube.face(Face::F)[1][1], Color::Red);
+    }
+
+    #[test]
+    fn turn_middle() {
+        let cube = Cube::new().mv(Turn::M(TurnType::Clock));
+
+        for row in 0..3 {
+            assert_eq!(cube.face(Face::F)[row], [Color::Green, Color::White, Color::Green]);
+            assert_eq!(cube.face(Face::D)[row], [Color::Yellow, Color::Green, Color::Yellow]);
+            assert_eq!(cube.face(Face::B)[row], [Color::Blue, Color::Yellow, Color::Blue]);
+            assert_eq!(cube.face(Face::U)[row], [Color::White, Color::Blue, Color::White]);
+        }
+        assert_eq!(cube.face(Face::L), [[Color::Orange; 3]; 3]);
+        assert_eq!(cube.face(Face::R), [[Color::Red; 3]; 3]);
+    }
+
+    #[test]
+    fn wide_turn_matches_face_turn_and_slice() {
+        let wide = Cube::new().mv(Turn::Rw(TurnType::Clock));
+        let split = Cube::new().mv(Turn::R(TurnType::Clock)).mv(Turn::M(TurnType::Prime));
+
+        assert_eq!(wide.facelets(), split.facelets());
+    }
 
     #[test]
     fn turn_top() {
         let cube = Cube::new().mv(Turn::U(TurnType::Clock));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Red; 3],
             vec![Color::Green; 6],
-            // Red
+            // Right
             vec![Color::Blue; 3],
             vec![Color::Red; 6],
-            // Blue
+            // Back
             vec![Color::Orange; 3],
             vec![Color::Blue; 6],
-            // Orange
+            // Left
             vec![Color::Green; 3],
             vec![Color::Orange; 6],
-            // White
+            // Up
             vec![Color::White; 9],
-            // Yellow
+            // Down
             vec![Color::Yellow; 9],
         ]
         .into_iter()
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -411,33 +674,33 @@ mod tests {
         let cube = Cube::new().mv(Turn::U(TurnType::Prime));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Orange; 3],
             vec![Color::Green; 6],
-            // Red
+            // Right
             vec![Color::Green; 3],
             vec![Color::Red; 6],
-            // Blue
+            // Back
             vec![Color::Red; 3],
             vec![Color::Blue; 6],
-            // Orange
+            // Left
             vec![Color::Blue; 3],
             vec![Color::Orange; 6],
-            // White
+            // Up
             vec![Color::White; 9],
-            // Yellow
+            // Down
             vec![Color::Yellow; 9],
         ]
         .into_iter()
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -451,33 +714,33 @@ mod tests {
         let cube = Cube::new().mv(Turn::U(TurnType::Double));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Blue; 3],
             vec![Color::Green; 6],
-            // Red
+            // Right
             vec![Color::Orange; 3],
             vec![Color::Red; 6],
-            // Blue
+            // Back
             vec![Color::Green; 3],
             vec![Color::Blue; 6],
-            // Orange
+            // Left
             vec![Color::Red; 3],
             vec![Color::Orange; 6],
-            // White
+            // Up
             vec![Color::White; 9],
-            // Yellow
+            // Down
             vec![Color::Yellow; 9],
         ]
         .into_iter()
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -491,33 +754,33 @@ mod tests {
         let cube = Cube::new().mv(Turn::D(TurnType::Clock));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 6],
             vec![Color::Orange; 3],
-            // Red
+            // Right
             vec![Color::Red; 6],
             vec![Color::Green; 3],
-            // Blue
+            // Back
             vec![Color::Blue; 6],
             vec![Color::Red; 3],
-            // Orange
+            // Left
             vec![Color::Orange; 6],
             vec![Color::Blue; 3],
-            // White
+            // Up
             vec![Color::White; 9],
-            // Yellow
+            // Down
             vec![Color::Yellow; 9],
         ]
         .into_iter()
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -531,33 +794,33 @@ mod tests {
         let cube = Cube::new().mv(Turn::D(TurnType::Prime));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 6],
             vec![Color::Red; 3],
-            // Red
+            // Right
             vec![Color::Red; 6],
             vec![Color::Blue; 3],
-            // Blue
+            // Back
             vec![Color::Blue; 6],
             vec![Color::Orange; 3],
-            // Orange
+            // Left
             vec![Color::Orange; 6],
             vec![Color::Green; 3],
-            // White
+            // Up
             vec![Color::White; 9],
-            // Yellow
+            // Down
             vec![Color::Yellow; 9],
         ]
         .into_iter()
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -571,33 +834,33 @@ mod tests {
         let cube = Cube::new().mv(Turn::D(TurnType::Double));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 6],
             vec![Color::Blue; 3],
-            // Red
+            // Right
             vec![Color::Red; 6],
             vec![Color::Orange; 3],
-            // Blue
+            // Back
             vec![Color::Blue; 6],
             vec![Color::Green; 3],
-            // Orange
+            // Left
             vec![Color::Orange; 6],
             vec![Color::Red; 3],
-            // White
+            // Up
             vec![Color::White; 9],
-            // Yellow
+            // Down
             vec![Color::Yellow; 9],
         ]
         .into_iter()
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -611,23 +874,23 @@ mod tests {
         let cube = Cube::new().mv(Turn::R(TurnType::Clock));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green, Color::Green, Color::Yellow],
             vec![Color::Green, Color::Green, Color::Yellow],
             vec![Color::Green, Color::Green, Color::Yellow],
-            // Red
+            // Right
             vec![Color::Red; 9],
-            // Blue
+            // Back
             vec![Color::White, Color::Blue, Color::Blue],
             vec![Color::White, Color::Blue, Color::Blue],
             vec![Color::White, Color::Blue, Color::Blue],
-            // Orange
+            // Left
             vec![Color::Orange; 9],
-            // White
+            // Up
             vec![Color::White, Color::White, Color::Green],
             vec![Color::White, Color::White, Color::Green],
             vec![Color::White, Color::White, Color::Green],
-            // Yellow
+            // Down
             vec![Color::Yellow, Color::Yellow, Color::Blue],
             vec![Color::Yellow, Color::Yellow, Color::Blue],
             vec![Color::Yellow, Color::Yellow, Color::Blue],
@@ -636,12 +899,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -655,23 +918,23 @@ mod tests {
         let cube = Cube::new().mv(Turn::R(TurnType::Prime));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green, Color::Green, Color::White],
             vec![Color::Green, Color::Green, Color::White],
             vec![Color::Green, Color::Green, Color::White],
-            // Red
+            // Right
             vec![Color::Red; 9],
-            // Blue
+            // Back
             vec![Color::Yellow, Color::Blue, Color::Blue],
             vec![Color::Yellow, Color::Blue, Color::Blue],
             vec![Color::Yellow, Color::Blue, Color::Blue],
-            // Orange
+            // Left
             vec![Color::Orange; 9],
-            // White
+            // Up
             vec![Color::White, Color::White, Color::Blue],
             vec![Color::White, Color::White, Color::Blue],
             vec![Color::White, Color::White, Color::Blue],
-            // Yellow
+            // Down
             vec![Color::Yellow, Color::Yellow, Color::Green],
             vec![Color::Yellow, Color::Yellow, Color::Green],
             vec![Color::Yellow, Color::Yellow, Color::Green],
@@ -680,12 +943,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -699,23 +962,23 @@ mod tests {
         let cube = Cube::new().mv(Turn::R(TurnType::Double));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green, Color::Green, Color::Blue],
             vec![Color::Green, Color::Green, Color::Blue],
             vec![Color::Green, Color::Green, Color::Blue],
-            // Red
+            // Right
             vec![Color::Red; 9],
-            // Blue
+            // Back
             vec![Color::Green, Color::Blue, Color::Blue],
             vec![Color::Green, Color::Blue, Color::Blue],
             vec![Color::Green, Color::Blue, Color::Blue],
-            // Orange
+            // Left
             vec![Color::Orange; 9],
-            // White
+            // Up
             vec![Color::White, Color::White, Color::Yellow],
             vec![Color::White, Color::White, Color::Yellow],
             vec![Color::White, Color::White, Color::Yellow],
-            // Yellow
+            // Down
             vec![Color::Yellow, Color::Yellow, Color::White],
             vec![Color::Yellow, Color::Yellow, Color::White],
             vec![Color::Yellow, Color::Yellow, Color::White],
@@ -724,12 +987,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -743,23 +1006,23 @@ mod tests {
         let cube = Cube::new().mv(Turn::L(TurnType::Clock));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::White, Color::Green, Color::Green],
             vec![Color::White, Color::Green, Color::Green],
             vec![Color::White, Color::Green, Color::Green],
-            // Red
+            // Right
             vec![Color::Red; 9],
-            // Blue
+            // Back
             vec![Color::Blue, Color::Blue, Color::Yellow],
             vec![Color::Blue, Color::Blue, Color::Yellow],
             vec![Color::Blue, Color::Blue, Color::Yellow],
-            // Orange
+            // Left
             vec![Color::Orange; 9],
-            // White
+            // Up
             vec![Color::Blue, Color::White, Color::White],
             vec![Color::Blue, Color::White, Color::White],
             vec![Color::Blue, Color::White, Color::White],
-            // Yellow
+            // Down
             vec![Color::Green, Color::Yellow, Color::Yellow],
             vec![Color::Green, Color::Yellow, Color::Yellow],
             vec![Color::Green, Color::Yellow, Color::Yellow],
@@ -768,12 +1031,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -787,23 +1050,23 @@ mod tests {
         let cube = Cube::new().mv(Turn::L(TurnType::Prime));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Yellow, Color::Green, Color::Green],
             vec![Color::Yellow, Color::Green, Color::Green],
             vec![Color::Yellow, Color::Green, Color::Green],
-            // Red
+            // Right
             vec![Color::Red; 9],
-            // Blue
+            // Back
             vec![Color::Blue, Color::Blue, Color::White],
             vec![Color::Blue, Color::Blue, Color::White],
             vec![Color::Blue, Color::Blue, Color::White],
-            // Orange
+            // Left
             vec![Color::Orange; 9],
-            // White
+            // Up
             vec![Color::Green, Color::White, Color::White],
             vec![Color::Green, Color::White, Color::White],
             vec![Color::Green, Color::White, Color::White],
-            // Yellow
+            // Down
             vec![Color::Blue, Color::Yellow, Color::Yellow],
             vec![Color::Blue, Color::Yellow, Color::Yellow],
             vec![Color::Blue, Color::Yellow, Color::Yellow],
@@ -812,12 +1075,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -831,23 +1094,23 @@ mod tests {
         let cube = Cube::new().mv(Turn::L(TurnType::Double));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Blue, Color::Green, Color::Green],
             vec![Color::Blue, Color::Green, Color::Green],
             vec![Color::Blue, Color::Green, Color::Green],
-            // Red
+            // Right
             vec![Color::Red; 9],
-            // Blue
+            // Back
             vec![Color::Blue, Color::Blue, Color::Green],
             vec![Color::Blue, Color::Blue, Color::Green],
             vec![Color::Blue, Color::Blue, Color::Green],
-            // Orange
+            // Left
             vec![Color::Orange; 9],
-            // White
+            // Up
             vec![Color::Yellow, Color::White, Color::White],
             vec![Color::Yellow, Color::White, Color::White],
             vec![Color::Yellow, Color::White, Color::White],
-            // Yellow
+            // Down
             vec![Color::White, Color::Yellow, Color::Yellow],
             vec![Color::White, Color::Yellow, Color::Yellow],
             vec![Color::White, Color::Yellow, Color::Yellow],
@@ -856,12 +1119,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -875,22 +1138,22 @@ mod tests {
         let cube = Cube::new().mv(Turn::F(TurnType::Clock));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 9],
-            // Red
+            // Right
             vec![Color::White, Color::Red, Color::Red],
             vec![Color::White, Color::Red, Color::Red],
             vec![Color::White, Color::Red, Color::Red],
-            // Blue
+            // Back
             vec![Color::Blue; 9],
-            // Orange
+            // Left
             vec![Color::Orange, Color::Orange, Color::Yellow],
             vec![Color::Orange, Color::Orange, Color::Yellow],
             vec![Color::Orange, Color::Orange, Color::Yellow],
-            // White
+            // Up
             vec![Color::White; 6],
             vec![Color::Orange; 3],
-            // Yellow
+            // Down
             vec![Color::Red; 3],
             vec![Color::Yellow; 6],
         ]
@@ -898,12 +1161,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -917,22 +1180,22 @@ mod tests {
         let cube = Cube::new().mv(Turn::F(TurnType::Prime));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 9],
-            // Red
+            // Right
             vec![Color::Yellow, Color::Red, Color::Red],
             vec![Color::Yellow, Color::Red, Color::Red],
             vec![Color::Yellow, Color::Red, Color::Red],
-            // Blue
+            // Back
             vec![Color::Blue; 9],
-            // Orange
+            // Left
             vec![Color::Orange, Color::Orange, Color::White],
             vec![Color::Orange, Color::Orange, Color::White],
             vec![Color::Orange, Color::Orange, Color::White],
-            // White
+            // Up
             vec![Color::White; 6],
             vec![Color::Red; 3],
-            // Yellow
+            // Down
             vec![Color::Orange; 3],
             vec![Color::Yellow; 6],
         ]
@@ -940,12 +1203,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -959,22 +1222,22 @@ mod tests {
         let cube = Cube::new().mv(Turn::F(TurnType::Double));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 9],
-            // Red
+            // Right
             vec![Color::Orange, Color::Red, Color::Red],
             vec![Color::Orange, Color::Red, Color::Red],
             vec![Color::Orange, Color::Red, Color::Red],
-            // Blue
+            // Back
             vec![Color::Blue; 9],
-            // Orange
+            // Left
             vec![Color::Orange, Color::Orange, Color::Red],
             vec![Color::Orange, Color::Orange, Color::Red],
             vec![Color::Orange, Color::Orange, Color::Red],
-            // White
+            // Up
             vec![Color::White; 6],
             vec![Color::Yellow; 3],
-            // Yellow
+            // Down
             vec![Color::White; 3],
             vec![Color::Yellow; 6],
         ]
@@ -982,12 +1245,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -1001,22 +1264,22 @@ mod tests {
         let cube = Cube::new().mv(Turn::B(TurnType::Clock));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 9],
-            // Red
+            // Right
             vec![Color::Red, Color::Red, Color::Yellow],
             vec![Color::Red, Color::Red, Color::Yellow],
             vec![Color::Red, Color::Red, Color::Yellow],
-            // Blue
+            // Back
             vec![Color::Blue; 9],
-            // Orange
+            // Left
             vec![Color::White, Color::Orange, Color::Orange],
             vec![Color::White, Color::Orange, Color::Orange],
             vec![Color::White, Color::Orange, Color::Orange],
-            // White
+            // Up
             vec![Color::Red; 3],
             vec![Color::White; 6],
-            // Yellow
+            // Down
             vec![Color::Yellow; 6],
             vec![Color::Orange; 3],
         ]
@@ -1024,12 +1287,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -1043,22 +1306,22 @@ mod tests {
         let cube = Cube::new().mv(Turn::B(TurnType::Prime));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 9],
-            // Red
+            // Right
             vec![Color::Red, Color::Red, Color::White],
             vec![Color::Red, Color::Red, Color::White],
             vec![Color::Red, Color::Red, Color::White],
-            // Blue
+            // Back
             vec![Color::Blue; 9],
-            // Orange
+            // Left
             vec![Color::Yellow, Color::Orange, Color::Orange],
             vec![Color::Yellow, Color::Orange, Color::Orange],
             vec![Color::Yellow, Color::Orange, Color::Orange],
-            // White
+            // Up
             vec![Color::Orange; 3],
             vec![Color::White; 6],
-            // Yellow
+            // Down
             vec![Color::Yellow; 6],
             vec![Color::Red; 3],
         ]
@@ -1066,12 +1329,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -1085,22 +1348,22 @@ mod tests {
         let cube = Cube::new().mv(Turn::B(TurnType::Double));
 
         let expected: Vec<Color> = vec![
-            // Green
+            // Front
             vec![Color::Green; 9],
-            // Red
+            // Right
             vec![Color::Red, Color::Red, Color::Orange],
             vec![Color::Red, Color::Red, Color::Orange],
             vec![Color::Red, Color::Red, Color::Orange],
-            // Blue
+            // Back
             vec![Color::Blue; 9],
-            // Orange
+            // Left
             vec![Color::Red, Color::Orange, Color::Orange],
             vec![Color::Red, Color::Orange, Color::Orange],
             vec![Color::Red, Color::Orange, Color::Orange],
-            // White
+            // Up
             vec![Color::Yellow; 3],
             vec![Color::White; 6],
-            // Yellow
+            // Down
             vec![Color::Yellow; 6],
             vec![Color::White; 3],
         ]
@@ -1108,12 +1371,12 @@ mod tests {
         .flatten()
         .collect();
         let cube_tiles: Vec<Color> = vec![
-            cube.green.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.red.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.blue.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.orange.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.white.tiles.into_iter().flatten().collect::<Vec<Color>>(),
-            cube.yellow.tiles.into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::F).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::R).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::B).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::L).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::U).into_iter().flatten().collect::<Vec<Color>>(),
+            cube.face(Face::D).into_iter().flatten().collect::<Vec<Color>>(),
         ]
         .into_iter()
         .flatten()
@@ -0,0 +1,575 @@
+//! Piece-level cube state: which corner and edge sits in each slot and how it is twisted or
+//! flipped.
+//!
+//! Corner slots are numbered `URF UFL ULB UBR DFR DLF DBL DRB` and edge slots
+//! `UR UF UL UB DR DF DL DB FR FL BL BR`. A solved cube has piece `i` in slot `i` with no twist or
+//! flip.
+
+use crate::cube::{Color, Cube};
+use crate::random;
+use crate::turn::{Turn, TurnType};
+use rand::Rng;
+
+/// The pieces of a cube, for solvers and analysis. States built outside the crate are always
+/// [validated](CubieCube::validate), so every piece is in range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CubieCube {
+    /// Corner in each corner slot.
+    pub(crate) cp: [u8; 8],
+    /// Twist of the corner in each slot, `0..3` clockwise from its U/D sticker.
+    pub(crate) co: [u8; 8],
+    /// Edge in each edge slot.
+    pub(crate) ep: [u8; 12],
+    /// Flip of the edge in each slot, `0..2`.
+    pub(crate) eo: [u8; 12],
+}
+
+const CORNER_NAMES: [&str; 8] = ["URF", "UFL", "ULB", "UBR", "DFR", "DLF", "DBL", "DRB"];
+
+const EDGE_NAMES: [&str; 12] =
+    ["UR", "UF", "UL", "UB", "DR", "DF", "DL", "DB", "FR", "FL", "BL", "BR"];
+
+/// Reasons a cube state cannot be reached from a solved cube.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidState {
+    /// A color is on some number of tiles other than nine.
+    StickerCount {
+        /// The color with too many or too few tiles.
+        color: Color,
+        /// How many tiles it is on.
+        count: usize,
+    },
+    /// Two faces have centers of the same color.
+    DuplicateCenter(Color),
+    /// The named corner slot holds a corner number above 7 or a twist above 2.
+    InvalidCorner(&'static str),
+    /// The named edge slot holds an edge number above 11 or a flip above 1.
+    InvalidEdge(&'static str),
+    /// Two centers that belong opposite each other are on adjacent faces.
+    MisplacedCenters,
+    /// The centers are those of a mirror image of the cube, e.g. with red and orange swapped.
+    MirroredCenters,
+    /// The tiles of the named corner slot match no corner piece.
+    UnknownCorner(&'static str),
+    /// The tiles of the named edge slot match no edge piece.
+    UnknownEdge(&'static str),
+    /// The named corner piece appears more than once.
+    DuplicateCorner(&'static str),
+    /// The named edge piece appears more than once.
+    DuplicateEdge(&'static str),
+    /// The corner twists do not add up to a multiple of three.
+    CornerTwist,
+    /// An odd number of edges are flipped.
+    EdgeFlip,
+    /// Exactly one of the corner and edge permutations is odd, as if two pieces were swapped.
+    Parity,
+}
+
+impl std::fmt::Display for InvalidState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidState::StickerCount { color, count } => {
+                write!(f, "{:?} is on {} tiles instead of 9", color, count)
+            },
+            InvalidState::DuplicateCenter(color) => {
+                write!(f, "more than one center is {:?}", color)
+            },
+            InvalidState::InvalidCorner(slot) => {
+                write!(f, "corner slot {} holds no corner or an impossible twist", slot)
+            },
+            InvalidState::InvalidEdge(slot) => {
+                write!(f, "edge slot {} holds no edge or an impossible flip", slot)
+            },
+            InvalidState::MisplacedCenters => write!(f, "opposite centers are on adjacent faces"),
+            InvalidState::MirroredCenters => write!(f, "the centers are in mirror image"),
+            InvalidState::UnknownCorner(slot) => {
+                write!(f, "the colors of corner {} do not match any corner", slot)
+            },
+            InvalidState::UnknownEdge(slot) => {
+                write!(f, "the colors of edge {} do not match any edge", slot)
+            },
+            InvalidState::DuplicateCorner(corner) => {
+                write!(f, "corner {} appears more than once", corner)
+            },
+            InvalidState::DuplicateEdge(edge) => write!(f, "edge {} appears more than once", edge),
+            InvalidState::CornerTwist => write!(f, "a corner is twisted in place"),
+            InvalidState::EdgeFlip => write!(f, "an edge is flipped in place"),
+            InvalidState::Parity => write!(f, "two pieces are swapped"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidState {}
+
+/// Facelets of each corner slot in `URFDLB` order, clockwise starting from the U or D sticker.
+const CORNER_FACELETS: [[usize; 3]; 8] = [
+    [8, 9, 20],
+    [6, 18, 38],
+    [0, 36, 47],
+    [2, 45, 11],
+    [29, 26, 15],
+    [27, 44, 24],
+    [33, 53, 42],
+    [35, 17, 51],
+];
+
+/// Facelets of each edge slot, starting from the U or D sticker, or the F or B one in the slice.
+const EDGE_FACELETS: [[usize; 2]; 12] = [
+    [5, 10],
+    [7, 19],
+    [3, 37],
+    [1, 46],
+    [32, 16],
+    [28, 25],
+    [30, 43],
+    [34, 52],
+    [23, 12],
+    [21, 41],
+    [50, 39],
+    [48, 14],
+];
+
+/// Home faces of each corner, indexed `U R F D L B`, in the order of [`CORNER_FACELETS`].
+const CORNER_FACES: [[usize; 3]; 8] =
+    [[0, 1, 2], [0, 2, 4], [0, 4, 5], [0, 5, 1], [3, 2, 1], [3, 4, 2], [3, 5, 4], [3, 1, 5]];
+
+/// Home faces of each edge, indexed `U R F D L B`, in the order of [`EDGE_FACELETS`].
+const EDGE_FACES: [[usize; 2]; 12] = [
+    [0, 1],
+    [0, 2],
+    [0, 4],
+    [0, 5],
+    [3, 1],
+    [3, 2],
+    [3, 4],
+    [3, 5],
+    [2, 1],
+    [2, 4],
+    [5, 4],
+    [5, 1],
+];
+
+/// Color of each face of a solved [`Cube`], indexed `U R F D L B`.
+const FACE_COLORS: [Color; 6] =
+    [Color::White, Color::Red, Color::Green, Color::Yellow, Color::Orange, Color::Blue];
+
+/// Clockwise quarter turns of `U R F D L B` in the replaced-by form: slot `i` receives the piece
+/// from slot `cp[i]`, adding `co[i]` to its twist.
+const FACE_TURNS: [CubieCube; 6] = [
+    CubieCube {
+        cp: [3, 0, 1, 2, 4, 5, 6, 7],
+        co: [0, 0, 0, 0, 0, 0, 0, 0],
+        ep: [3, 0, 1, 2, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [4, 1, 2, 0, 7, 5, 6, 3],
+        co: [2, 0, 0, 1, 1, 0, 0, 2],
+        ep: [8, 1, 2, 3, 11, 5, 6, 7, 4, 9, 10, 0],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [1, 5, 2, 3, 0, 4, 6, 7],
+        co: [1, 2, 0, 0, 2, 1, 0, 0],
+        ep: [0, 9, 2, 3, 4, 8, 6, 7, 1, 5, 10, 11],
+        eo: [0, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 2, 3, 5, 6, 7, 4],
+        co: [0, 0, 0, 0, 0, 0, 0, 0],
+        ep: [0, 1, 2, 3, 5, 6, 7, 4, 8, 9, 10, 11],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 2, 6, 3, 4, 1, 5, 7],
+        co: [0, 1, 2, 0, 0, 2, 1, 0],
+        ep: [0, 1, 10, 3, 4, 5, 9, 7, 8, 2, 6, 11],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 3, 7, 4, 5, 2, 6],
+        co: [0, 0, 1, 2, 0, 0, 2, 1],
+        ep: [0, 1, 2, 11, 4, 5, 6, 10, 8, 9, 3, 7],
+        eo: [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 1],
+    },
+];
+
+/// The middle slices turning along with the `x y z` rotations, i.e. `M' E' S`. Centers are not
+/// tracked, so only edges move.
+const ROTATION_SLICES: [CubieCube; 3] = [
+    CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [0, 5, 2, 1, 4, 7, 6, 3, 8, 9, 10, 11],
+        eo: [0, 1, 0, 1, 0, 1, 0, 1, 0, 0, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 4, 5, 6, 7, 11, 8, 9, 10],
+        eo: [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1],
+    },
+    CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [2, 1, 6, 3, 0, 5, 4, 7, 8, 9, 10, 11],
+        eo: [1, 0, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0],
+    },
+];
+
+impl CubieCube {
+    /// Every piece in its own slot, unturned.
+    pub const SOLVED: CubieCube = CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    };
+
+    /// The state with corner `cp[i]` in slot `i`, twisted `co[i]` times clockwise, and edge
+    /// `ep[i]`, flipped if `eo[i]` is 1, once [validated](CubieCube::validate).
+    pub fn new(cp: [u8; 8], co: [u8; 8], ep: [u8; 12], eo: [u8; 12]) -> Result<Self, InvalidState> {
+        let cube = CubieCube { cp, co, ep, eo };
+        cube.validate()?;
+
+        Ok(cube)
+    }
+
+    /// Corner in each corner slot.
+    pub fn cp(&self) -> &[u8; 8] {
+        &self.cp
+    }
+
+    /// Twist of the corner in each slot, `0..3` clockwise from its U/D sticker.
+    pub fn co(&self) -> &[u8; 8] {
+        &self.co
+    }
+
+    /// Edge in each edge slot.
+    pub fn ep(&self) -> &[u8; 12] {
+        &self.ep
+    }
+
+    /// Flip of the edge in each slot, `0..2`.
+    pub fn eo(&self) -> &[u8; 12] {
+        &self.eo
+    }
+
+    /// A state drawn uniformly from every solvable cube state.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut cube = CubieCube::SOLVED;
+        random::shuffle(rng, &mut cube.cp);
+        random::shuffle(rng, &mut cube.ep);
+        if permutation_parity(&cube.cp) != permutation_parity(&cube.ep) {
+            cube.ep.swap(0, 1);
+        }
+
+        for i in 0..7 {
+            cube.co[i] = random::below(rng, 3) as u8;
+        }
+        cube.co[7] = (3 - cube.co[..7].iter().sum::<u8>() % 3) % 3;
+        for i in 0..11 {
+            cube.eo[i] = random::below(rng, 2) as u8;
+        }
+        cube.eo[11] = cube.eo[..11].iter().sum::<u8>() % 2;
+
+        cube
+    }
+
+    /// Reads the pieces off a facelet cube, identifying faces by their center colors.
+    pub fn from_cube(cube: &Cube) -> Result<Self, InvalidState> {
+        let facelets = cube.facelets();
+        let centers = [0, 1, 2, 3, 4, 5].map(|f| facelets[f * 9 + 4]);
+        if let Some(f) = (1..6).find(|&f| centers[..f].contains(&centers[f])) {
+            return Err(InvalidState::DuplicateCenter(centers[f]));
+        }
+        let faces = facelets.map(|c| centers.iter().position(|&center| center == c).unwrap());
+
+        let mut cubie = CubieCube::SOLVED;
+        for (slot, slot_facelets) in CORNER_FACELETS.iter().enumerate() {
+            let unknown = InvalidState::UnknownCorner(CORNER_NAMES[slot]);
+            let colors = slot_facelets.map(|f| faces[f]);
+            let twist = colors.iter().position(|&f| f == 0 || f == 3).ok_or(unknown)?;
+            let turned = [0, 1, 2].map(|k| colors[(twist + k) % 3]);
+            cubie.cp[slot] = CORNER_FACES.iter().position(|&c| c == turned).ok_or(unknown)? as u8;
+            cubie.co[slot] = twist as u8;
+        }
+        for (slot, slot_facelets) in EDGE_FACELETS.iter().enumerate() {
+            let colors = slot_facelets.map(|f| faces[f]);
+            let (edge, flip) = EDGE_FACES
+                .iter()
+                .enumerate()
+                .find_map(|(e, &c)| {
+                    if c == colors {
+                        Some((e, 0))
+                    } else if c == [colors[1], colors[0]] {
+                        Some((e, 1))
+                    } else {
+                        None
+                    }
+                })
+                .ok_or(InvalidState::UnknownEdge(EDGE_NAMES[slot]))?;
+            cubie.ep[slot] = edge as u8;
+            cubie.eo[slot] = flip;
+        }
+
+        Ok(cubie)
+    }
+
+    /// Checks that every piece is in range and that this state can be reached from a solved cube
+    /// by turning faces.
+    pub fn validate(&self) -> Result<(), InvalidState> {
+        if let Some(slot) = (0..8).find(|&i| self.cp[i] >= 8 || self.co[i] >= 3) {
+            return Err(InvalidState::InvalidCorner(CORNER_NAMES[slot]));
+        }
+        if let Some(slot) = (0..12).find(|&i| self.ep[i] >= 12 || self.eo[i] >= 2) {
+            return Err(InvalidState::InvalidEdge(EDGE_NAMES[slot]));
+        }
+        if let Some(corner) = (0..8).find(|c| self.cp.iter().filter(|&p| p == c).count() > 1) {
+            return Err(InvalidState::DuplicateCorner(CORNER_NAMES[corner as usize]));
+        }
+        if let Some(edge) = (0..12).find(|e| self.ep.iter().filter(|&p| p == e).count() > 1) {
+            return Err(InvalidState::DuplicateEdge(EDGE_NAMES[edge as usize]));
+        }
+        if self.co.iter().sum::<u8>() % 3 != 0 {
+            return Err(InvalidState::CornerTwist);
+        }
+        if self.eo.iter().sum::<u8>() % 2 != 0 {
+            return Err(InvalidState::EdgeFlip);
+        }
+        if permutation_parity(&self.cp) != permutation_parity(&self.ep) {
+            return Err(InvalidState::Parity);
+        }
+
+        Ok(())
+    }
+
+    /// The facelet cube showing this state in the standard color scheme.
+    pub fn to_cube(self) -> Cube {
+        let mut faces = [0; 54];
+        for (i, face) in faces.iter_mut().enumerate() {
+            *face = i / 9;
+        }
+        for (slot, slot_facelets) in CORNER_FACELETS.iter().enumerate() {
+            let twist = self.co[slot] as usize;
+            for (k, &face) in CORNER_FACES[self.cp[slot] as usize].iter().enumerate() {
+                faces[slot_facelets[(k + twist) % 3]] = face;
+            }
+        }
+        for (slot, slot_facelets) in EDGE_FACELETS.iter().enumerate() {
+            let flip = self.eo[slot] as usize;
+            for (k, &face) in EDGE_FACES[self.ep[slot] as usize].iter().enumerate() {
+                faces[slot_facelets[(k + flip) % 2]] = face;
+            }
+        }
+
+        Cube::from_facelets(&faces.map(|f| FACE_COLORS[f]))
+    }
+
+    /// The state reached by applying `other` after `self`.
+    pub fn multiply(&self, other: &CubieCube) -> CubieCube {
+        let mut product = CubieCube::SOLVED;
+        for i in 0..8 {
+            let from = other.cp[i] as usize;
+            product.cp[i] = self.cp[from];
+            product.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+        for i in 0..12 {
+            let from = other.ep[i] as usize;
+            product.ep[i] = self.ep[from];
+            product.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+
+        product
+    }
+
+    /// The state that undoes this one.
+    pub fn inverse(&self) -> CubieCube {
+        let mut inverse = CubieCube::SOLVED;
+        for i in 0..8 {
+            let corner = self.cp[i] as usize;
+            inverse.cp[corner] = i as u8;
+            inverse.co[corner] = (3 - self.co[i]) % 3;
+        }
+        for i in 0..12 {
+            let edge = self.ep[i] as usize;
+            inverse.ep[edge] = i as u8;
+            inverse.eo[edge] = self.eo[i];
+        }
+
+        inverse
+    }
+
+    /// Applies `turn`. Rotations relabel the pieces instead of moving them, since this state is
+    /// always read relative to the centers; slice and wide turns are applied through
+    /// [`Turn::expand`].
+    pub fn mv(&self, turn: Turn) -> Self {
+        let (quarter, rotation) = match turn {
+            Turn::U(_) => (FACE_TURNS[0], false),
+            Turn::R(_) => (FACE_TURNS[1], false),
+            Turn::F(_) => (FACE_TURNS[2], false),
+            Turn::D(_) => (FACE_TURNS[3], false),
+            Turn::L(_) => (FACE_TURNS[4], false),
+            Turn::B(_) => (FACE_TURNS[5], false),
+            Turn::X(_) => (rotation(Turn::R(TurnType::Clock), 0, Turn::L(TurnType::Prime)), true),
+            Turn::Y(_) => (rotation(Turn::U(TurnType::Clock), 1, Turn::D(TurnType::Prime)), true),
+            Turn::Z(_) => (rotation(Turn::F(TurnType::Clock), 2, Turn::B(TurnType::Prime)), true),
+            _ => return turn.expand().into_iter().fold(*self, |cube, t| cube.mv(t)),
+        };
+        let turned =
+            (0..turn.turn_type().quarters()).fold(CubieCube::SOLVED, |c, _| c.multiply(&quarter));
+
+        if rotation {
+            turned.inverse().multiply(self).multiply(&turned)
+        } else {
+            self.multiply(&turned)
+        }
+    }
+}
+
+/// A clockwise whole cube rotation as a move of the pieces: both outer layers plus the slice in
+/// between, given as an index into [`ROTATION_SLICES`].
+fn rotation(first: Turn, slice: usize, second: Turn) -> CubieCube {
+    CubieCube::SOLVED.mv(first).multiply(&ROTATION_SLICES[slice]).mv(second)
+}
+
+impl Default for CubieCube {
+    fn default() -> Self {
+        CubieCube::SOLVED
+    }
+}
+
+/// `true` for odd permutations.
+pub(crate) fn permutation_parity(perm: &[u8]) -> bool {
+    let mut odd = false;
+    for i in 0..perm.len() {
+        for j in i + 1..perm.len() {
+            if perm[j] < perm[i] {
+                odd = !odd;
+            }
+        }
+    }
+
+    odd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarter_turns_have_order_four() {
+        for turn in Turn::ALL {
+            let cube = (0..4).fold(CubieCube::SOLVED, |c, _| c.mv(turn));
+            assert_eq!(cube, CubieCube::SOLVED, "{}", turn);
+        }
+    }
+
+    #[test]
+    fn sexy_move_has_order_six() {
+        let sexy = [
+            Turn::R(TurnType::Clock),
+            Turn::U(TurnType::Clock),
+            Turn::R(TurnType::Prime),
+            Turn::U(TurnType::Prime),
+        ];
+        let once = sexy.iter().fold(CubieCube::SOLVED, |c, &t| c.mv(t));
+
+        assert_ne!(once, CubieCube::SOLVED);
+        assert_eq!((0..6).fold(CubieCube::SOLVED, |c, _| c.multiply(&once)), CubieCube::SOLVED);
+    }
+
+    #[test]
+    fn turns_agree_with_facelet_cube() {
+        for turn in Turn::ALL {
+            let cube = Cube::new().mv(turn);
+            let cubie = CubieCube::SOLVED.mv(turn);
+
+            assert_eq!(CubieCube::from_cube(&cube), Ok(cubie), "{}", turn);
+            if Turn::FACE_TURNS.contains(&turn) {
+                assert_eq!(cubie.to_cube().facelets(), cube.facelets(), "{}", turn);
+            }
+        }
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        let mut rng = random::seeded(1);
+        for _ in 0..100 {
+            let cubie = CubieCube::random(&mut rng);
+            assert_eq!(CubieCube::from_cube(&cubie.to_cube()), Ok(cubie));
+        }
+    }
+
+    #[test]
+    fn sequences_agree_with_facelet_cube() {
+        let mut rng = random::seeded(2);
+        let mut turns: Vec<Turn> = (0..50).map(|_| rng.gen()).collect();
+        turns.extend(Turn::ALL);
+        turns.extend((0..50).map(|_| rng.gen::<Turn>()));
+        let cube = turns.iter().fold(Cube::new(), |c, &t| c.mv(t));
+        let cubie = turns.iter().fold(CubieCube::SOLVED, |c, &t| c.mv(t));
+
+        assert_eq!(CubieCube::from_cube(&cube), Ok(cubie));
+    }
+
+    #[test]
+    fn random_states_are_solvable() {
+        let mut rng = random::seeded(3);
+        for _ in 0..100 {
+            let cube = CubieCube::random(&mut rng);
+            assert_eq!(cube.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn rejects_impossible_states() {
+        let mut cube = CubieCube::SOLVED;
+        cube.co[0] = 1;
+        assert_eq!(cube.validate(), Err(InvalidState::CornerTwist));
+
+        let mut cube = CubieCube::SOLVED;
+        cube.eo[3] = 1;
+        assert_eq!(cube.validate(), Err(InvalidState::EdgeFlip));
+
+        let mut cube = CubieCube::SOLVED;
+        cube.ep.swap(0, 1);
+        assert_eq!(cube.validate(), Err(InvalidState::Parity));
+
+        let mut cube = CubieCube::SOLVED;
+        cube.cp[1] = 0;
+        assert_eq!(cube.validate(), Err(InvalidState::DuplicateCorner("URF")));
+    }
+
+    #[test]
+    fn rejects_out_of_range_pieces() {
+        let solved = CubieCube::SOLVED;
+        let mut co = solved.co;
+        co[0] = 3;
+        assert_eq!(
+            CubieCube::new(solved.cp, co, solved.ep, solved.eo),
+            Err(InvalidState::InvalidCorner("URF"))
+        );
+
+        let mut cp = solved.cp;
+        cp[5] = 8;
+        assert_eq!(
+            CubieCube::new(cp, solved.co, solved.ep, solved.eo),
+            Err(InvalidState::InvalidCorner("DLF"))
+        );
+
+        let mut eo = solved.eo;
+        eo[2] = 2;
+        eo[3] = 2;
+        assert_eq!(
+            CubieCube::new(solved.cp, solved.co, solved.ep, eo),
+            Err(InvalidState::InvalidEdge("UL"))
+        );
+
+        let turned = CubieCube::SOLVED.mv(Turn::R(TurnType::Clock));
+        assert_eq!(
+            CubieCube::new(*turned.cp(), *turned.co(), *turned.ep(), *turned.eo()),
+            Ok(turned)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! The error type shared by the library and the command line.
+
+use crate::cube::InvalidState;
+use crate::notation::ParseError;
+use std::io;
+use std::path::PathBuf;
+
+/// Everything that can go wrong, from reading arguments to writing the output.
+#[derive(Debug)]
+pub enum Error {
+    /// An option or setting has a value that cannot be used, e.g. an unknown color.
+    Argument {
+        /// What the value is for, e.g. `color`.
+        name: String,
+        /// The value as given.
+        value: String,
+        /// Why it cannot be used.
+        reason: String,
+    },
+    /// Moves or facelets could not be read.
+    Parse {
+        /// What was being read, e.g. `moves`.
+        name: &'static str,
+        /// The text as given.
+        input: String,
+        /// What was wrong with it, and where.
+        error: ParseError,
+    },
+    /// A state cannot be reached from a solved cube by turning faces.
+    InvalidState(InvalidState),
+    /// A file, or standard output if there is no path, could not be read or written.
+    Io {
+        /// The file, or `None` for standard output.
+        path: Option<PathBuf>,
+        /// The underlying failure.
+        error: io::Error,
+    },
+}
+
+/// A result whose error is [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// An [`Error::Argument`] with `value` for `name` rejected for `reason`.
+    pub fn argument(name: &str, value: &str, reason: impl Into<String>) -> Self {
+        Error::Argument { name: name.to_string(), value: value.to_string(), reason: reason.into() }
+    }
+
+    /// The status to exit with, following the BSD `sysexits.h` codes: 64 for bad arguments, 65
+    /// for bad input data and 74 for I/O errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Argument { .. } => 64,
+            Error::Parse { .. } | Error::InvalidState(_) => 65,
+            Error::Io { .. } => 74,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Argument { name, value, reason } => {
+                write!(f, "invalid {} '{}': {}", name, value, reason)
+            },
+            Error::Parse { name, input, error } => {
+                write!(f, "malformed {} '{}': {}", name, input, error)
+            },
+            Error::InvalidState(error) => write!(f, "unreachable state: {}", error),
+            Error::Io { path: Some(path), error } => write!(f, "'{}': {}", path.display(), error),
+            Error::Io { path: None, error } => write!(f, "standard output: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Argument { .. } => None,
+            Error::Parse { error, .. } => Some(error),
+            Error::InvalidState(error) => Some(error),
+            Error::Io { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<InvalidState> for Error {
+    fn from(error: InvalidState) -> Self {
+        Error::InvalidState(error)
+    }
+}
+
+/// I/O on standard output, which has no path.
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io { path: None, error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_errors() {
+        let parse = "R X".parse::<crate::Algorithm>().unwrap_err();
+        let errors = [
+            Error::argument("gap", "-1", "expected a natural number"),
+            Error::Parse { name: "moves", input: "R X".to_string(), error: parse.clone() },
+            InvalidState::Parity.into(),
+            Error::Io {
+                path: Some("sheet.pdf".into()),
+                error: io::Error::new(io::ErrorKind::PermissionDenied, "permission denied"),
+            },
+        ];
+
+        assert_eq!(
+            errors.iter().map(|e| (e.to_string(), e.exit_code())).collect::<Vec<_>>(),
+            [
+                ("invalid gap '-1': expected a natural number".to_string(), 64),
+                (format!("malformed moves 'R X': {}", parse), 65),
+                ("unreachable state: two pieces are swapped".to_string(), 65),
+                ("'sheet.pdf': permission denied".to_string(), 74),
+            ]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Iterative deepening A* search over face turns of a [`CubieCube`].
+//!
+//! The heuristic is the larger of the twist and flip pruning tables of the two-phase solver: the
+//! turns needed to orient the pieces and gather the slice edges, which no solution can skip.
+
+use crate::cubie::CubieCube;
+use crate::turn::Turn;
+use crate::twophase::coord;
+use crate::twophase::tables::{tables, Tables};
+
+/// Finds a shortest sequence of face turns that solves `cube`, searching no deeper than
+/// `max_depth` turns.
+///
+/// The heuristic rarely exceeds 9 turns, so the search stays exponential in how far the solution
+/// goes beyond that and only suits states a dozen or so turns from solved.
+pub fn solve(cube: &CubieCube, max_depth: u32) -> Option<Vec<Turn>> {
+    let mut search = Search { tables: tables(), cube: *cube, path: vec![] };
+    let twist = coord::twist(cube);
+    let flip = coord::flip(cube);
+    let slice = coord::slice(cube);
+    let corners = coord::corners(cube);
+
+    (0..=max_depth)
+        .find(|&depth| search.search(twist, flip, slice, corners, depth))
+        .map(|_| search.path)
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    cube: CubieCube,
+    path: Vec<Turn>,
+}
+
+impl Search<'_> {
+    fn search(
+        &mut self,
+        twist: usize,
+        flip: usize,
+        slice: usize,
+        corners: usize,
+        togo: u32,
+    ) -> bool {
+        let t = self.tables;
+        let dist = t.twist_slice_prune[twist * coord::N_SLICE + slice]
+            .max(t.flip_slice_prune[flip * coord::N_SLICE + slice]) as u32;
+        if dist > togo {
+            return false;
+        }
+        if togo == 0 {
+            // Only the edge permutation is left unchecked by the coordinates.
+            return corners == 0 && self.solves();
+        }
+
+        for (m, turn) in Turn::FACE_TURNS.into_iter().enumerate() {
+            if self.path.last().map_or(false, |prev| turn.is_redundant_after(prev)) {
+                continue;
+            }
+
+            self.path.push(turn);
+            let found = self.search(
+                t.twist_move[twist][m] as usize,
+                t.flip_move[flip][m] as usize,
+                t.slice_move[slice][m] as usize,
+                t.corners_move[corners][m] as usize,
+                togo - 1,
+            );
+            if found {
+                return true;
+            }
+            self.path.pop();
+        }
+
+        false
+    }
+
+    fn solves(&self) -> bool {
+        self.path.iter().fold(self.cube, |c, &t| c.mv(t)) == CubieCube::SOLVED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::turn::TurnType;
+
+    #[test]
+    fn solved_cube_needs_no_turns() {
+        assert_eq!(solve(&CubieCube::SOLVED, 0), Some(vec![]));
+    }
+
+    #[test]
+    fn finds_shortest_solution() {
+        let cube = [Turn::R(TurnType::Clock), Turn::U(TurnType::Double), Turn::F(TurnType::Prime)]
+            .into_iter()
+            .fold(CubieCube::SOLVED, |c, t| c.mv(t));
+
+        let solution = solve(&cube, 3).unwrap();
+        assert_eq!(
+            solution,
+            vec![Turn::F(TurnType::Clock), Turn::U(TurnType::Double), Turn::R(TurnType::Prime)]
+        );
+        assert_eq!(solution.into_iter().fold(cube, |c, t| c.mv(t)), CubieCube::SOLVED);
+    }
+
+    #[test]
+    fn respects_depth_limit() {
+        let cube = CubieCube::SOLVED.mv(Turn::R(TurnType::Clock)).mv(Turn::U(TurnType::Clock));
+
+        assert!(solve(&cube, 1).is_none());
+    }
+}
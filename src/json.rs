@@ -0,0 +1,69 @@
+//! Machine-readable output of scrambles and states.
+
+use crate::cube::Cube;
+use crate::metric::Metric;
+use crate::turn::Turn;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A batch of generated scrambles, with what it takes to generate them again.
+#[derive(Serialize)]
+pub struct Scrambles<'a> {
+    /// The algorithm argument, e.g. `two-phase`.
+    pub generator: &'a str,
+    /// What `--seed` reproduces the batch with.
+    pub seed: u64,
+    /// One entry per scramble, in order.
+    pub scrambles: Vec<Entry<'a>>,
+}
+
+/// The moves made on a cube, the state they lead to and their length in every metric.
+#[derive(Serialize)]
+pub struct Entry<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Label on a scramble sheet, e.g. `E1`; left out if there is none.
+    pub label: Option<&'a str>,
+    /// The moves made, in WCA notation.
+    pub moves: &'a [Turn],
+    /// The state reached, as a facelet string.
+    pub facelets: String,
+    /// Length in each metric, keyed by its abbreviation.
+    pub length: BTreeMap<String, usize>,
+}
+
+impl<'a> Entry<'a> {
+    /// Describes the moves made on `cube` and where they lead.
+    pub fn new(cube: &'a Cube, label: Option<&'a str>) -> Self {
+        Entry {
+            label,
+            moves: cube.moves(),
+            facelets: cube.to_facelet_string(),
+            length: Metric::ALL.map(|m| (m.to_string(), m.length(cube.moves()))).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::turn::TurnType;
+
+    #[test]
+    fn describes_state() {
+        let cube = Cube::new().mv(Turn::R(TurnType::Clock)).mv(Turn::M(TurnType::Double));
+        let scrambles = Scrambles {
+            generator: "rand",
+            seed: 7,
+            scrambles: vec![Entry::new(&cube, Some("E1"))],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&scrambles).unwrap(),
+            r#"{"generator":"rand","seed":7,"scrambles":[{"label":"E1","moves":["R","M2"],"#
+                .to_owned()
+                + &format!(r#""facelets":"{}","#, cube.to_facelet_string())
+                + r#""length":{"ETM":2,"HTM":3,"QTM":5,"STM":2}}]}"#
+        );
+        assert!(!serde_json::to_string(&Entry::new(&cube, None)).unwrap().contains("label"));
+    }
+}
@@ -0,0 +1,47 @@
+//! Scramble generation for the Rubik's Cube.
+//!
+//! Scrambles are lists of [`Turn`]s drawn by the generators in [`scramble`], either random moves
+//! or random states solved backwards. A [`Cube`] applies turns to the colors of its stickers, and
+//! [`render`] draws it as text, SVG, PNG or PDF scramble sheets. [`CubieCube`] holds the same state
+//! as pieces, for solvers and analysis.
+//!
+//! ```
+//! use scrambler::{random, scramble, Cube, Metric};
+//!
+//! // The same seed gives the same scramble on every platform.
+//! let mut rng = random::seeded(2024);
+//! let turns = scramble::random_turns(&mut rng, 20);
+//! let cube = turns.iter().fold(Cube::new(), |cube, &turn| cube.mv(turn));
+//!
+//! assert_eq!(Metric::Htm.length(cube.moves()), 20);
+//! assert!(cube.validate().is_ok());
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod algorithm;
+pub mod cube;
+pub mod cubie;
+pub mod error;
+mod ida;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod metric;
+pub mod notation;
+pub mod orientation;
+pub mod random;
+pub mod render;
+pub mod scramble;
+pub mod state;
+pub mod turn;
+mod twophase;
+
+pub use crate::algorithm::Algorithm;
+pub use crate::cube::{Color, Cube, Face, InvalidState};
+pub use crate::cubie::CubieCube;
+pub use crate::error::{Error, Result};
+pub use crate::metric::Metric;
+pub use crate::notation::ParseError;
+pub use crate::orientation::Orientation;
+pub use crate::state::State;
+pub use crate::turn::{Turn, TurnType};
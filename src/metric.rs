@@ -0,0 +1,105 @@
+//! Ways of counting the length of a move sequence.
+
+use crate::error::Error;
+use crate::turn::Turn;
+use std::str::FromStr;
+
+/// A way of counting moves, parsed from and displayed as its abbreviation, e.g. `HTM`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Half turn metric: any turn of an outer layer counts one, slices count two.
+    Htm,
+    /// Quarter turn metric: like HTM, but half turns count two.
+    Qtm,
+    /// Slice turn metric: any turn of a layer counts one.
+    Stm,
+    /// Execution turn metric: every move, rotations included, counts one.
+    Etm,
+}
+
+impl Metric {
+    /// Every metric, in the order they are listed.
+    pub const ALL: [Metric; 4] = [Metric::Htm, Metric::Qtm, Metric::Stm, Metric::Etm];
+
+    /// Cost of a single turn. Rotations are free in every metric but ETM.
+    pub fn cost(&self, turn: &Turn) -> usize {
+        let quarters = match turn.turn_type().quarters() {
+            2 => 2,
+            _ => 1,
+        };
+        let (rotation, slice) = match turn {
+            Turn::X(_) | Turn::Y(_) | Turn::Z(_) => (true, false),
+            Turn::M(_) | Turn::E(_) | Turn::S(_) => (false, true),
+            _ => (false, false),
+        };
+
+        match self {
+            Metric::Etm => 1,
+            _ if rotation => 0,
+            Metric::Stm => 1,
+            Metric::Htm if slice => 2,
+            Metric::Htm => 1,
+            Metric::Qtm if slice => 2 * quarters,
+            Metric::Qtm => quarters,
+        }
+    }
+
+    /// Sum of the costs of `turns`.
+    pub fn length(&self, turns: &[Turn]) -> usize {
+        turns.iter().map(|t| self.cost(t)).sum()
+    }
+}
+
+impl FromStr for Metric {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Metric::ALL
+            .into_iter()
+            .find(|m| m.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::argument("metric", s, "expected one of HTM, QTM, STM, ETM"))
+    }
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Htm => write!(f, "HTM"),
+            Metric::Qtm => write!(f, "QTM"),
+            Metric::Stm => write!(f, "STM"),
+            Metric::Etm => write!(f, "ETM"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_turns;
+
+    fn lengths(s: &str) -> [usize; 4] {
+        let turns = parse_turns(s).unwrap();
+        Metric::ALL.map(|m| m.length(&turns))
+    }
+
+    #[test]
+    fn counts_face_turns() {
+        assert_eq!(lengths("R U R' U2"), [4, 5, 4, 4]);
+        assert_eq!(lengths(""), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn counts_slices_wide_turns_and_rotations() {
+        assert_eq!(lengths("M2"), [2, 4, 1, 1]);
+        assert_eq!(lengths("E'"), [2, 2, 1, 1]);
+        assert_eq!(lengths("Rw2 u"), [2, 3, 2, 2]);
+        assert_eq!(lengths("x y2 z'"), [0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!("qtm".parse::<Metric>().unwrap(), Metric::Qtm);
+        assert_eq!("ETM".parse::<Metric>().unwrap(), Metric::Etm);
+        assert!("otm".parse::<Metric>().is_err());
+    }
+}
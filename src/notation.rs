@@ -0,0 +1,388 @@
+//! Parsing of move sequences written in WCA notation, e.g. `R U R' U2`, along with the
+//! commutators `[A, B]`, conjugates `[A: B]` and repeated groups `(A)n` of alg sheets.
+
+use crate::turn::{Turn, TurnType};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// Where and why input could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Character offset of the offending input.
+    pub position: usize,
+    /// The offending character, or `None` at the end of the input.
+    pub found: Option<char>,
+    /// What would have been read there, e.g. `a turn`.
+    pub expected: &'static str,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.found {
+            Some(c) => {
+                write!(f, "expected {} at position {}, found '{}'", self.expected, self.position, c)
+            },
+            None => write!(
+                f,
+                "expected {} at position {}, found end of input",
+                self.expected, self.position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses whitespace-separated turns. Separators may be left out, so `RUR'U'` is accepted too.
+///
+/// Commutators `[A, B]` expand to `A B A' B'`, conjugates `[A: B]` to `A B A'` and groups `(A)3` to
+/// `A A A`. They can be nested, e.g. `[U: ([R, D])2]`.
+pub fn parse_turns(s: &str) -> Result<Vec<Turn>, ParseError> {
+    let mut parser = Parser::new(s);
+    let turns = parser.sequence(0)?;
+    if parser.skip_whitespace() {
+        return Err(parser.error(EXPECTED_TURN));
+    }
+
+    Ok(turns)
+}
+
+impl FromStr for Turn {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let turn = parser.turn()?;
+        match parser.next() {
+            None => Ok(turn),
+            Some((position, c)) => {
+                Err(ParseError { position, found: Some(c), expected: "end of turn" })
+            },
+        }
+    }
+}
+
+/// A turn missing its [`TurnType`], e.g. `Turn::R`.
+type Layer = fn(TurnType) -> Turn;
+
+const EXPECTED_TURN: &str = "a turn (U D R L F B, Uw or u, M E S, x y z)";
+
+/// Upper bound on the count of a repeated group, to keep typos from eating all memory.
+const MAX_REPETITIONS: usize = 100;
+
+/// Upper bound on the length of the whole sequence once expanded, as nested groups multiply.
+const MAX_TURNS: usize = 10_000;
+
+const EXPECTED_LENGTH: &str = "at most 10000 turns once expanded";
+
+fn inverse(turns: &[Turn]) -> Vec<Turn> {
+    turns.iter().rev().map(Turn::inverse).collect()
+}
+
+/// Fails at the bracket or group opened at `start` unless `before` turns expanded ahead of it and
+/// `len` more fit in [`MAX_TURNS`].
+fn check_length(start: (usize, char), before: usize, len: usize) -> Result<(), ParseError> {
+    match before.checked_add(len) {
+        Some(total) if total <= MAX_TURNS => Ok(()),
+        _ => Err(ParseError { position: start.0, found: Some(start.1), expected: EXPECTED_LENGTH }),
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    /// Number of characters consumed so far.
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser { chars: s.chars().peekable(), position: 0 }
+    }
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        let position = self.position;
+        self.chars.next().map(|c| {
+            self.position += 1;
+            (position, c)
+        })
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// Returns whether any input is left.
+    fn skip_whitespace(&mut self) -> bool {
+        while self.peek().map_or(false, char::is_whitespace) {
+            self.next();
+        }
+
+        self.peek().is_some()
+    }
+
+    fn error(&mut self, expected: &'static str) -> ParseError {
+        ParseError { position: self.position, found: self.peek(), expected }
+    }
+
+    /// Consumes `c` after optional whitespace.
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.peek() != Some(c) {
+            return Err(self.error(expected));
+        }
+        self.next();
+
+        Ok(())
+    }
+
+    /// Reads turns, groups and brackets up to the end of the input or of the enclosing bracket.
+    /// `before` turns have already been expanded ahead of it.
+    fn sequence(&mut self, before: usize) -> Result<Vec<Turn>, ParseError> {
+        let mut turns = vec![];
+        while self.skip_whitespace() {
+            match self.peek() {
+                Some('(') => turns.extend(self.group(before + turns.len())?),
+                Some('[') => turns.extend(self.bracket(before + turns.len())?),
+                Some(')') | Some(']') | Some(',') | Some(':') => break,
+                _ => {
+                    let start = (self.position, self.peek().unwrap());
+                    check_length(start, before + turns.len(), 1)?;
+                    turns.push(self.turn()?);
+                },
+            }
+        }
+
+        Ok(turns)
+    }
+
+    /// Reads `(A)` with an optional repetition count.
+    fn group(&mut self, before: usize) -> Result<Vec<Turn>, ParseError> {
+        let start = self.next().unwrap();
+        let turns = self.sequence(before)?;
+        self.expect(')', "')'")?;
+
+        let mut count = 0;
+        let mut digits = false;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            self.next();
+            count = count * 10 + digit as usize;
+            digits = true;
+            if count > MAX_REPETITIONS {
+                return Err(ParseError {
+                    position: self.position - 1,
+                    found: Some(char::from_digit(digit, 10).unwrap()),
+                    expected: "a repetition count of at most 100",
+                });
+            }
+        }
+
+        let count = if digits { count } else { 1 };
+        check_length(start, before, turns.len().saturating_mul(count))?;
+
+        Ok(turns.repeat(count))
+    }
+
+    /// Reads a commutator `[A, B]` or a conjugate `[A: B]`.
+    fn bracket(&mut self, before: usize) -> Result<Vec<Turn>, ParseError> {
+        let start = self.next().unwrap();
+        let a = self.sequence(before)?;
+        self.skip_whitespace();
+        let commutator = match self.peek() {
+            Some(',') => true,
+            Some(':') => false,
+            _ => return Err(self.error("',' or ':'")),
+        };
+        self.next();
+        let b = self.sequence(before + a.len())?;
+        self.expect(']', "']'")?;
+        let len = 2 * a.len() + if commutator { 2 } else { 1 } * b.len();
+        check_length(start, before, len)?;
+
+        let mut turns = [a.as_slice(), &b, &inverse(&a)].concat();
+        if commutator {
+            turns.extend(inverse(&b));
+        }
+
+        Ok(turns)
+    }
+
+    /// Reads a face, slice or rotation letter. Wide turns are written `Rw` or `r`.
+    fn turn(&mut self) -> Result<Turn, ParseError> {
+        let (layer, wide): (Layer, Layer) = match self.peek() {
+            Some('U') => (Turn::U, Turn::Uw),
+            Some('D') => (Turn::D, Turn::Dw),
+            Some('R') => (Turn::R, Turn::Rw),
+            Some('L') => (Turn::L, Turn::Lw),
+            Some('F') => (Turn::F, Turn::Fw),
+            Some('B') => (Turn::B, Turn::Bw),
+            Some('u') => (Turn::Uw, Turn::Uw),
+            Some('d') => (Turn::Dw, Turn::Dw),
+            Some('r') => (Turn::Rw, Turn::Rw),
+            Some('l') => (Turn::Lw, Turn::Lw),
+            Some('f') => (Turn::Fw, Turn::Fw),
+            Some('b') => (Turn::Bw, Turn::Bw),
+            Some('M') => (Turn::M, Turn::M),
+            Some('E') => (Turn::E, Turn::E),
+            Some('S') => (Turn::S, Turn::S),
+            Some('x') => (Turn::X, Turn::X),
+            Some('y') => (Turn::Y, Turn::Y),
+            Some('z') => (Turn::Z, Turn::Z),
+            _ => return Err(self.error(EXPECTED_TURN)),
+        };
+        let face = self.next().map_or(false, |(_, c)| "UDRLFB".contains(c));
+
+        let layer = if face && self.peek() == Some('w') {
+            self.next();
+            wide
+        } else {
+            layer
+        };
+
+        Ok(layer(self.turn_type()))
+    }
+
+    /// Reads an optional `'` or `2` suffix. `2'` is accepted as a double turn.
+    fn turn_type(&mut self) -> TurnType {
+        match self.peek() {
+            Some('\'') | Some('’') => {
+                self.next();
+                TurnType::Prime
+            },
+            Some('2') => {
+                self.next();
+                if let Some('\'') | Some('’') = self.peek() {
+                    self.next();
+                }
+                TurnType::Double
+            },
+            _ => TurnType::Clock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sequence() {
+        assert_eq!(
+            parse_turns("R U R' U2  F2' B"),
+            Ok(vec![
+                Turn::R(TurnType::Clock),
+                Turn::U(TurnType::Clock),
+                Turn::R(TurnType::Prime),
+                Turn::U(TurnType::Double),
+                Turn::F(TurnType::Double),
+                Turn::B(TurnType::Clock),
+            ])
+        );
+        assert_eq!(parse_turns("RUR'"), parse_turns("R U R'"));
+        assert_eq!(parse_turns("  "), Ok(vec![]));
+    }
+
+    #[test]
+    fn parses_slices_wide_turns_and_rotations() {
+        assert_eq!(
+            parse_turns("M2 E' S Rw r' Lw2 l x y' z2"),
+            Ok(vec![
+                Turn::M(TurnType::Double),
+                Turn::E(TurnType::Prime),
+                Turn::S(TurnType::Clock),
+                Turn::Rw(TurnType::Clock),
+                Turn::Rw(TurnType::Prime),
+                Turn::Lw(TurnType::Double),
+                Turn::Lw(TurnType::Clock),
+                Turn::X(TurnType::Clock),
+                Turn::Y(TurnType::Prime),
+                Turn::Z(TurnType::Double),
+            ])
+        );
+        assert_eq!(
+            parse_turns("Mw"),
+            Err(ParseError { position: 1, found: Some('w'), expected: EXPECTED_TURN })
+        );
+    }
+
+    #[test]
+    fn expands_commutators_conjugates_and_groups() {
+        let parse = |s| parse_turns(s).unwrap();
+
+        assert_eq!(parse("[R U R', D]"), parse("R U R' D R U' R' D'"));
+        assert_eq!(parse("[U: R U R']"), parse("U R U R' U'"));
+        assert_eq!(parse("(R U R' U')3"), parse("R U R' U' R U R' U' R U R' U'"));
+        assert_eq!(parse("(R U)"), parse("R U"));
+        assert_eq!(parse("(R)0 U"), parse("U"));
+        assert_eq!(parse("[U: [R, D]] F"), parse("U R D R' D' U' F"));
+        assert_eq!(parse("[[R: U], (D)2]"), parse("R U R' D D R U' R' D' D'"));
+        assert_eq!(parse("[ R U ,D ]"), parse("[R U, D]"));
+    }
+
+    #[test]
+    fn reports_unbalanced_brackets() {
+        assert_eq!(
+            parse_turns("[R U R' D]"),
+            Err(ParseError { position: 9, found: Some(']'), expected: "',' or ':'" })
+        );
+        assert_eq!(
+            parse_turns("[R, U"),
+            Err(ParseError { position: 5, found: None, expected: "']'" })
+        );
+        assert_eq!(
+            parse_turns("(R U]"),
+            Err(ParseError { position: 4, found: Some(']'), expected: "')'" })
+        );
+        assert_eq!(
+            parse_turns("R U)"),
+            Err(ParseError { position: 3, found: Some(')'), expected: EXPECTED_TURN })
+        );
+        assert_eq!(
+            parse_turns("(R)1000"),
+            Err(ParseError {
+                position: 6,
+                found: Some('0'),
+                expected: "a repetition count of at most 100"
+            })
+        );
+    }
+
+    #[test]
+    fn limits_expanded_length() {
+        assert_eq!(parse_turns("((R U)100)50").map(|turns| turns.len()), Ok(10_000));
+        assert_eq!(
+            parse_turns("((((((R)100)100)100)100)100)"),
+            Err(ParseError { position: 3, found: Some('('), expected: EXPECTED_LENGTH })
+        );
+        assert_eq!(
+            parse_turns("R ((R U)100)50"),
+            Err(ParseError { position: 2, found: Some('('), expected: EXPECTED_LENGTH })
+        );
+        assert_eq!(
+            parse_turns("[((R U)100)25: (R)100]"),
+            Err(ParseError { position: 0, found: Some('['), expected: EXPECTED_LENGTH })
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["U", "D'", "R2", "M", "E'", "S2", "Fw", "Bw'", "x2", "y", "z'"] {
+            assert_eq!(s.parse::<Turn>().map(|t| t.to_string()), Ok(s.to_string()));
+        }
+    }
+
+    #[test]
+    fn reports_bad_token() {
+        assert_eq!(
+            parse_turns("R U X"),
+            Err(ParseError { position: 4, found: Some('X'), expected: EXPECTED_TURN })
+        );
+        assert_eq!(
+            "R'2".parse::<Turn>(),
+            Err(ParseError { position: 2, found: Some('2'), expected: "end of turn" })
+        );
+        assert_eq!(
+            "".parse::<Turn>(),
+            Err(ParseError { position: 0, found: None, expected: EXPECTED_TURN })
+        );
+    }
+}
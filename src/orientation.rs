@@ -0,0 +1,90 @@
+//! Ways of holding the cube.
+
+use crate::cube::Color;
+use crate::error::Error;
+use crate::render::Scheme;
+
+/// The colors of the centers facing up and front. Cubes start out held white up, green front, and
+/// moves are made on whichever faces are up, front and so on at the time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orientation {
+    up: Color,
+    front: Color,
+}
+
+impl Orientation {
+    /// Fails unless the two colors are on adjacent faces.
+    pub fn new(up: Color, front: Color) -> Result<Self, Error> {
+        if up == front || up == front.opposite() {
+            let value = format!("{:?}-{:?}", up, front).to_lowercase();
+            return Err(Error::argument("orientation", &value, "the colors are not adjacent"));
+        }
+
+        Ok(Orientation { up, front })
+    }
+
+    /// Color of the center on top.
+    pub fn up(&self) -> Color {
+        self.up
+    }
+
+    /// Color of the center facing the viewer.
+    pub fn front(&self) -> Color {
+        self.front
+    }
+
+    /// Reads `UP-FRONT` color names, e.g. `yellow-red`, as shown in `scheme`.
+    pub fn parse(s: &str, scheme: &Scheme) -> Result<Self, Error> {
+        let invalid = |reason: String| Error::argument("orientation", s, reason);
+        let (up, front) = s
+            .split_once('-')
+            .ok_or_else(|| invalid("expected UP-FRONT colors, e.g. yellow-red".to_string()))?;
+        let find = |name: &str| {
+            scheme.find(name).ok_or_else(|| invalid(format!("unknown color '{}'", name)))
+        };
+
+        Orientation::new(find(up)?, find(front)?)
+            .map_err(|_| invalid("the colors are not adjacent".to_string()))
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Orientation { up: Color::White, front: Color::Green }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_adjacent_colors() {
+        let scheme = Scheme::western();
+        let parse =
+            |s: &str, scheme: &Scheme| Orientation::parse(s, scheme).map_err(|e| e.to_string());
+
+        assert_eq!(parse("white-green", &scheme), Ok(Orientation::default()));
+        assert_eq!(
+            parse("Yellow-red", &scheme),
+            Ok(Orientation { up: Color::Yellow, front: Color::Red })
+        );
+        assert_eq!(
+            parse("blue-red", &Scheme::japanese()),
+            Ok(Orientation { up: Color::Yellow, front: Color::Red })
+        );
+        assert_eq!(
+            parse("blue-white", &Scheme::japanese()),
+            Err("invalid orientation 'blue-white': the colors are not adjacent".to_string())
+        );
+        assert_eq!(
+            Orientation::new(Color::White, Color::Yellow).unwrap_err().to_string(),
+            "invalid orientation 'white-yellow': the colors are not adjacent"
+        );
+        assert_eq!(
+            parse("pink-red", &scheme),
+            Err("invalid orientation 'pink-red': unknown color 'pink'".to_string())
+        );
+        assert!(parse("yellow", &scheme).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! Images of the unfolded cube, laid out like [`Cube::print`]: `U` on top, then `L F R B`, then
+//! `D`.
+
+mod pdf;
+mod png;
+mod scheme;
+mod svg;
+mod text;
+
+pub use self::pdf::{pdf, Sheet};
+pub use self::png::png;
+pub use self::scheme::{Paint, Scheme};
+pub use self::svg::svg;
+pub use self::text::{TextMode, TextRenderer};
+
+use crate::cube::{Color, Cube};
+
+/// A shade as red, green and blue channels.
+pub type Rgb = [u8; 3];
+
+/// Sizes are in pixels, or points for documents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Style {
+    /// Side of a sticker.
+    pub sticker: u32,
+    /// Space between stickers and around the net; faces are twice as far apart.
+    pub gap: u32,
+    /// Paint of each color.
+    pub scheme: Scheme,
+}
+
+impl Style {
+    /// The shade `color` is painted in.
+    pub fn rgb(&self, color: Color) -> Rgb {
+        self.scheme.paint(color).rgb
+    }
+
+    fn face_size(&self) -> u32 {
+        self.sticker.saturating_add(self.gap).saturating_mul(3).saturating_add(self.gap)
+    }
+
+    /// Width and height of the whole net, or `u32::MAX` where they do not fit.
+    pub fn size(&self) -> (u32, u32) {
+        (self.face_size().saturating_mul(4), self.face_size().saturating_mul(3))
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style { sticker: 30, gap: 2, scheme: Scheme::western() }
+    }
+}
+
+/// A sticker's square, with its top left corner at `x`, `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sticker {
+    /// Distance from the left of the net.
+    pub x: u32,
+    /// Distance from the top of the net.
+    pub y: u32,
+    /// Shade to fill the square with.
+    pub color: Rgb,
+}
+
+/// Column and row of each face of the net, in `URFDLB` order.
+const FACE_POSITIONS: [(u32, u32); 6] = [(1, 0), (2, 1), (1, 1), (1, 2), (0, 1), (3, 1)];
+
+/// Every sticker of `cube` placed on the net.
+pub fn stickers(cube: &Cube, style: &Style) -> Vec<Sticker> {
+    // Sizes saturate rather than overflow, so that absurd styles give absurd but harmless nets.
+    let cell = style.sticker.saturating_add(style.gap);
+    let offset = |face: u32, tile: u32| {
+        face.saturating_mul(style.face_size())
+            .saturating_add(style.gap)
+            .saturating_add(cell.saturating_mul(tile))
+    };
+    cube.facelets()
+        .iter()
+        .enumerate()
+        .map(|(i, &color)| {
+            let (column, row) = FACE_POSITIONS[i / 9];
+            let (tile_row, tile_column) = ((i % 9 / 3) as u32, (i % 3) as u32);
+
+            Sticker {
+                x: offset(column, tile_column),
+                y: offset(row, tile_row),
+                color: style.rgb(color),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn net_matches_print_layout() {
+        let style = Style { sticker: 10, gap: 1, ..Style::default() };
+        let stickers = stickers(&Cube::new(), &style);
+
+        assert_eq!(style.size(), (136, 102));
+        // First tile of U sits right of the left face, first tile of L below U.
+        assert_eq!(stickers[0], Sticker { x: 35, y: 1, color: style.rgb(Color::White) });
+        assert_eq!(stickers[36], Sticker { x: 1, y: 35, color: style.rgb(Color::Orange) });
+        // Last tile of B ends one gap before the right edge.
+        let last = stickers[53];
+        assert_eq!((last.x + style.sticker + style.gap, last.color), (136, style.rgb(Color::Blue)));
+    }
+}
@@ -0,0 +1,305 @@
+use super::{stickers, Style};
+use crate::cube::Cube;
+use crate::error::Error;
+use std::fmt::Write;
+
+/// A competition scramble sheet: a header naming the event, round and group, then a row for each
+/// scramble with its label, moves and net.
+#[derive(Clone)]
+pub struct Sheet {
+    /// Name of the event, e.g. `3x3x3 Cube`.
+    pub event: String,
+    /// Round number, counting from 1.
+    pub round: u32,
+    /// Group name, e.g. `A`.
+    pub group: String,
+    /// Labels, e.g. `1` or `E1`, and the scrambled cubes, whose moves are the scrambles.
+    pub scrambles: Vec<(String, Cube)>,
+}
+
+/// A4, in points.
+const PAGE_SIZE: (u32, u32) = (595, 842);
+const MARGIN: u32 = 40;
+const HEADER_HEIGHT: u32 = 70;
+const ROW_HEIGHT: u32 = 86;
+const ROWS_PER_PAGE: usize = 8;
+const LABEL_WIDTH: u32 = 40;
+const MOVES_SIZE: f32 = 11.0;
+/// Long scrambles are set smaller, down to this size, to fit in their row.
+const MIN_MOVES_SIZE: f32 = 6.0;
+/// Room below the first line of moves for further lines, clear of the rule under the row.
+const MOVES_HEIGHT: f32 = (ROW_HEIGHT - 30) as f32;
+/// Courier is monospaced, which makes wrapping the moves simple.
+const COURIER_WIDTH: f32 = 0.6;
+const FONTS: [&str; 3] = ["Helvetica", "Helvetica-Bold", "Courier"];
+
+/// A PDF of the sheet, continued over as many pages as needed. Stickers are drawn as vectors with
+/// the colors of `style`; its sizes are replaced to fit the rows. Fails if a scramble has too many
+/// moves to fit in its row, which takes a few hundred.
+pub fn pdf(sheet: &Sheet, style: &Style) -> Result<Vec<u8>, Error> {
+    let style = Style { sticker: 7, gap: 1, ..style.clone() };
+    let pages: Vec<&[(String, Cube)]> = if sheet.scrambles.is_empty() {
+        vec![&[]]
+    } else {
+        sheet.scrambles.chunks(ROWS_PER_PAGE).collect()
+    };
+
+    let contents = pages
+        .iter()
+        .enumerate()
+        .map(|(page, rows)| {
+            let mut content = header(sheet, page + 1, pages.len());
+            for (row, (label, cube)) in rows.iter().enumerate() {
+                content.push_str(&scramble_row(row as u32, label, cube, &style)?);
+            }
+
+            Ok(content)
+        })
+        .collect::<Result<Vec<String>, Error>>()?;
+
+    Ok(document(&contents))
+}
+
+fn header(sheet: &Sheet, page: usize, pages: usize) -> String {
+    let (width, height) = PAGE_SIZE;
+    let mut subtitle = format!("Round {}    Group {}", sheet.round, sheet.group);
+    if pages > 1 {
+        write!(subtitle, "    Page {} of {}", page, pages).unwrap();
+    }
+
+    let mut content = String::new();
+    text(&mut content, "F2", 20.0, MARGIN, height - MARGIN - 20, &sheet.event);
+    text(&mut content, "F1", 12.0, MARGIN, height - MARGIN - 42, &subtitle);
+    let rule = height - MARGIN - HEADER_HEIGHT + 10;
+    writeln!(content, "1 w {} {} m {} {} l S", MARGIN, rule, width - MARGIN, rule).unwrap();
+
+    content
+}
+
+fn scramble_row(row: u32, label: &str, cube: &Cube, style: &Style) -> Result<String, Error> {
+    let (width, height) = PAGE_SIZE;
+    let (net_width, net_height) = style.size();
+    let top = height - MARGIN - HEADER_HEIGHT - row * ROW_HEIGHT;
+    let net_x = width - MARGIN - net_width;
+    let mut content = String::new();
+
+    text(&mut content, "F2", 14.0, MARGIN, top - 24, &format!("{}.", label));
+
+    let moves = cube.moves().iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    let (size, lines) = fit_moves(&moves, net_x - MARGIN - LABEL_WIDTH - 10).ok_or_else(|| {
+        let reason = format!("its {} moves do not fit on a sheet", moves.len());
+        Error::argument("scramble", label, reason)
+    })?;
+    for (i, line) in lines.iter().enumerate() {
+        let y = top - 24 - (i as f32 * line_height(size)).round() as u32;
+        text(&mut content, "F3", size, MARGIN + LABEL_WIDTH, y, line);
+    }
+
+    let net_top = top - (ROW_HEIGHT - net_height) / 2;
+    writeln!(content, "0 G 0.5 w").unwrap();
+    for sticker in stickers(cube, style) {
+        let [r, g, b] = sticker.color.map(|c| c as f32 / 255.0);
+        let y = net_top - sticker.y - style.sticker;
+        writeln!(
+            content,
+            "{:.3} {:.3} {:.3} rg {} {} {size} {size} re B",
+            r,
+            g,
+            b,
+            net_x + sticker.x,
+            y,
+            size = style.sticker
+        )
+        .unwrap();
+    }
+
+    let bottom = top - ROW_HEIGHT;
+    writeln!(content, "0.5 G 0.5 w {} {} m {} {} l S", MARGIN, bottom, width - MARGIN, bottom)
+        .unwrap();
+
+    Ok(content)
+}
+
+/// The largest whole font size, from [`MOVES_SIZE`] down to [`MIN_MOVES_SIZE`], at which the moves
+/// wrap into `width` points and [`MOVES_HEIGHT`], with the wrapped lines.
+fn fit_moves(moves: &[String], width: u32) -> Option<(f32, Vec<String>)> {
+    let sizes = (MIN_MOVES_SIZE as u32..=MOVES_SIZE as u32).rev().map(|size| size as f32);
+    sizes.into_iter().find_map(|size| {
+        let lines = wrap(moves, (width as f32 / (COURIER_WIDTH * size)) as usize);
+        let height = lines.len().saturating_sub(1) as f32 * line_height(size);
+
+        (height <= MOVES_HEIGHT).then_some((size, lines))
+    })
+}
+
+/// Distance between the baselines of lines of moves.
+fn line_height(size: f32) -> f32 {
+    size * 15.0 / 11.0
+}
+
+/// Joins words into lines of at most `columns` characters, unless a single word is longer.
+fn wrap(words: &[String], columns: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for word in words {
+        match lines.last_mut() {
+            Some(line) if line.len() + 1 + word.len() <= columns => {
+                line.push(' ');
+                line.push_str(word);
+            },
+            _ => lines.push(word.clone()),
+        }
+    }
+
+    lines
+}
+
+fn text(content: &mut String, font: &str, size: f32, x: u32, y: u32, s: &str) {
+    writeln!(content, "BT /{} {} Tf {} {} Td ({}) Tj ET", font, size, x, y, escape(s)).unwrap();
+}
+
+/// Escapes a PDF string, writing characters beyond ASCII as Latin-1 octal codes and any that do
+/// not fit as `?`.
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '(' | ')' | '\\' => write!(escaped, "\\{}", c).unwrap(),
+            ' '..='~' => escaped.push(c),
+            '\u{a0}'..='\u{ff}' => write!(escaped, "\\{:03o}", c as u32).unwrap(),
+            _ => escaped.push('?'),
+        }
+    }
+
+    escaped
+}
+
+/// Wraps page contents into a PDF file, with a cross-reference table pointing at every object.
+fn document(contents: &[String]) -> Vec<u8> {
+    let (width, height) = PAGE_SIZE;
+    let first_page = 3 + FONTS.len();
+    let kids: Vec<String> =
+        (0..contents.len()).map(|i| format!("{} 0 R", first_page + 2 * i)).collect();
+    let fonts: Vec<String> =
+        (0..FONTS.len()).map(|i| format!("/F{} {} 0 R", i + 1, i + 3)).collect();
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), contents.len()),
+    ];
+    for font in FONTS {
+        objects.push(format!(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+            font
+        ));
+    }
+    for (i, content) in contents.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {} >> >> \
+             /Contents {} 0 R >>",
+            width,
+            height,
+            fonts.join(" "),
+            first_page + 2 * i + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}\nendstream", content.len(), content));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = vec![];
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object).unwrap();
+    }
+    let xref = pdf.len();
+    write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).unwrap();
+    for offset in offsets {
+        writeln!(pdf, "{:010} 00000 n ", offset).unwrap();
+    }
+    write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    )
+    .unwrap();
+
+    pdf.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::turn::Turn;
+
+    fn sheet(scrambles: usize) -> Sheet {
+        let cube = Turn::FACE_TURNS.into_iter().fold(Cube::new(), |c, t| c.mv(t));
+        Sheet {
+            event: "3x3x3 (Cube)".to_string(),
+            round: 1,
+            group: "A".to_string(),
+            scrambles: (1..=scrambles).map(|i| (i.to_string(), cube.clone())).collect(),
+        }
+    }
+
+    #[test]
+    fn cross_references_point_at_objects() {
+        let pdf = String::from_utf8(pdf(&sheet(7), &Style::default()).unwrap()).unwrap();
+        let xref = pdf.rsplit("startxref\n").next().unwrap().lines().next().unwrap();
+        let table = &pdf[xref.parse::<usize>().unwrap()..];
+
+        assert!(pdf.starts_with("%PDF-1.4\n") && pdf.ends_with("%%EOF\n"));
+        assert!(table.starts_with("xref\n0 8\n"));
+        for (i, entry) in table.lines().skip(3).take(7).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", i + 1)));
+        }
+    }
+
+    #[test]
+    fn lays_out_header_and_rows() {
+        let pdf = String::from_utf8(pdf(&sheet(7), &Style::default()).unwrap()).unwrap();
+
+        assert!(pdf.contains("/Count 1 "));
+        assert!(pdf.contains("(3x3x3 \\(Cube\\)) Tj"));
+        assert!(pdf.contains("(Round 1    Group A) Tj"));
+        assert_eq!(pdf.matches(" re B").count(), 7 * 54);
+        assert!(pdf.contains("(7.) Tj"));
+        assert!(pdf.contains("BT /F3 11 Tf"));
+    }
+
+    #[test]
+    fn continues_on_more_pages() {
+        let pdf = String::from_utf8(pdf(&sheet(9), &Style::default()).unwrap()).unwrap();
+
+        assert!(pdf.contains("/Count 2 "));
+        assert!(pdf.contains("(Round 1    Group A    Page 2 of 2) Tj"));
+    }
+
+    #[test]
+    fn shrinks_long_scrambles_to_fit() {
+        let turns = crate::scramble::random_turns(&mut crate::random::seeded(3), 150);
+        let long = turns.into_iter().fold(Cube::new(), |c, t| c.mv(t));
+        let mut sheet = sheet(0);
+        sheet.scrambles.push(("1".to_string(), long.clone()));
+        let pdf = String::from_utf8(pdf(&sheet, &Style::default()).unwrap()).unwrap();
+
+        // At 11 points the moves would run past the bottom of the row.
+        assert!(pdf.contains("BT /F3 8 Tf") && !pdf.contains("BT /F3 11 Tf"));
+
+        let turns = crate::scramble::random_turns(&mut crate::random::seeded(3), 400);
+        sheet.scrambles[0].1 = turns.into_iter().fold(long, |c, t| c.mv(t));
+        assert_eq!(
+            super::pdf(&sheet, &Style::default()).unwrap_err().to_string(),
+            "invalid scramble '1': its 550 moves do not fit on a sheet"
+        );
+    }
+
+    #[test]
+    fn wraps_and_escapes_text() {
+        let words: Vec<String> = ["R", "U'", "F2", "D"].map(String::from).to_vec();
+
+        assert_eq!(wrap(&words, 5), ["R U'", "F2 D"]);
+        assert_eq!(wrap(&words, 1), ["R", "U'", "F2", "D"]);
+        assert_eq!(escape("Café (Open) \\ ✓"), "Caf\\351 \\(Open\\) \\\\ ?");
+    }
+}
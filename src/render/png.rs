@@ -0,0 +1,76 @@
+use super::{stickers, Style};
+use crate::cube::Cube;
+use crate::error::Error;
+
+/// Images are kept well within memory, at 256 MiB of pixel data.
+const MAX_PIXELS: u32 = 1 << 26;
+
+/// A PNG image of the net, with a transparent background. Fails if the style's sizes make the
+/// image empty or too large.
+pub fn png(cube: &Cube, style: &Style) -> Result<Vec<u8>, Error> {
+    let (width, height) = style.size();
+    if width == 0 || height == 0 || width.saturating_mul(height) > MAX_PIXELS {
+        let size = format!("{}x{}", width, height);
+        return Err(Error::argument("image size", &size, "expected 1 to 2^26 pixels"));
+    }
+
+    let mut pixels = vec![0; (width * height * 4) as usize];
+    for sticker in stickers(cube, style) {
+        let [r, g, b] = sticker.color;
+        for y in sticker.y..sticker.y + style.sticker {
+            let start = (y * width + sticker.x) as usize * 4;
+            let end = start + style.sticker as usize * 4;
+            for pixel in pixels[start..end].chunks_mut(4) {
+                pixel.copy_from_slice(&[r, g, b, u8::MAX]);
+            }
+        }
+    }
+
+    let mut png = vec![];
+    let mut encoder = ::png::Encoder::new(&mut png, width, height);
+    encoder.set_color(::png::ColorType::Rgba);
+    encoder.set_depth(::png::BitDepth::Eight);
+    let mut writer = encoder.write_header().expect("writing to memory cannot fail");
+    writer.write_image_data(&pixels).expect("pixels match the header");
+    writer.finish().expect("writing to memory cannot fail");
+
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Color;
+    use crate::turn::{Turn, TurnType};
+
+    #[test]
+    fn decodes_to_net() {
+        let style = Style { sticker: 4, gap: 1, ..Style::default() };
+        let png = png(&Cube::new().mv(Turn::R(TurnType::Clock)), &style).unwrap();
+
+        let decoder = ::png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        let pixel = |x: u32, y: u32| {
+            let i = (y * info.width + x) as usize * 4;
+            [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+        };
+
+        assert_eq!((info.width, info.height), style.size());
+        // Background, then the top left tile of U, then the top right one, which R turned green.
+        assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+        let [r, g, b] = style.rgb(Color::White);
+        assert_eq!(pixel(17, 1), [r, g, b, 255]);
+        let [r, g, b] = style.rgb(Color::Green);
+        assert_eq!(pixel(27, 4), [r, g, b, 255]);
+    }
+
+    #[test]
+    fn rejects_empty_and_huge_images() {
+        let cube = Cube::new();
+
+        assert!(png(&cube, &Style { sticker: 0, gap: 0, ..Style::default() }).is_err());
+        assert!(png(&cube, &Style { sticker: u32::MAX, ..Style::default() }).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use super::Rgb;
+use crate::cube::{Color, Face};
+use crate::error::Error;
+use std::str::FromStr;
+
+/// How a sticker is drawn: its shade, and the letter standing for it in text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Paint {
+    /// Letter printed in text mode.
+    pub letter: char,
+    /// Shade drawn in nets.
+    pub rgb: Rgb,
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint { letter: color.letter(), rgb: color.rgb() }
+    }
+}
+
+/// The paint on each face of a solved cube. Cubes tell stickers apart by the [`Color`] they have
+/// in the western scheme, so a scheme only changes how they look, never how they move.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scheme {
+    /// Indexed by [`Face`].
+    faces: [Paint; 6],
+}
+
+impl Scheme {
+    /// White opposite yellow and green opposite blue, with red right of green.
+    pub fn western() -> Self {
+        Scheme { faces: Face::ALL.map(|face| face.solved_color().into()) }
+    }
+
+    /// As western, but with blue opposite white and yellow opposite green.
+    pub fn japanese() -> Self {
+        let mut scheme = Scheme::western();
+        scheme.set(Face::D, Color::Blue.into());
+        scheme.set(Face::B, Color::Yellow.into());
+
+        scheme
+    }
+
+    /// How stickers of `color` are drawn.
+    pub fn paint(&self, color: Color) -> Paint {
+        self.faces[color.home() as usize]
+    }
+
+    /// The stickers painted in the color named, going by the letter it is shown with. In the
+    /// Japanese scheme, `blue` finds the stickers belonging on `D`, i.e. [`Color::Yellow`].
+    pub fn find(&self, name: &str) -> Option<Color> {
+        let letter = Color::from_name(name)?.letter();
+        Color::ALL.into_iter().find(|&c| self.paint(c).letter == letter)
+    }
+
+    /// Repaints the stickers belonging on `face`.
+    pub fn set(&mut self, face: Face, paint: Paint) {
+        self.faces[face as usize] = paint;
+    }
+
+    /// Applies a `FACE=COLOR` setting, where the color is the name of one of the six usual colors
+    /// or a `#RRGGBB` shade, optionally followed by the letter to show in text. Shades keep the
+    /// letter of the face's paint unless one is given.
+    pub fn apply_setting(&mut self, setting: &str) -> Result<(), Error> {
+        self.setting(setting).map_err(|reason| Error::argument("face color", setting, reason))
+    }
+
+    fn setting(&mut self, setting: &str) -> Result<(), String> {
+        let malformed = || "expected FACE=COLOR".to_string();
+        let (face, value) = setting.split_once('=').ok_or_else(malformed)?;
+        let face = face.trim();
+        let face = match face.chars().next().and_then(Face::from_letter) {
+            Some(f) if face.len() == 1 => f,
+            _ => return Err(format!("unknown face '{}', expected one of U R F D L B", face)),
+        };
+        let mut words = value.split_whitespace();
+        let color = words.next().ok_or_else(malformed)?;
+        let mut paint = match color.strip_prefix('#') {
+            Some(hex) => Paint { letter: self.faces[face as usize].letter, rgb: rgb(hex)? },
+            None => {
+                Color::from_name(color).ok_or_else(|| format!("unknown color '{}'", color))?.into()
+            },
+        };
+        match (words.next(), words.next()) {
+            (None, _) => {},
+            (Some(letter), None) if letter.chars().count() == 1 => {
+                paint.letter = letter.chars().next().unwrap();
+            },
+            _ => return Err(malformed()),
+        }
+
+        self.set(face, paint);
+        Ok(())
+    }
+}
+
+impl Default for Scheme {
+    fn default() -> Self {
+        Scheme::western()
+    }
+}
+
+/// Reads a scheme file: `FACE=COLOR` settings as for [`Scheme::apply_setting`], one per line, over
+/// the western scheme. Blank lines and lines starting with `#` are ignored.
+impl FromStr for Scheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scheme = Scheme::western();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                scheme.setting(line).map_err(|reason| {
+                    Error::argument(&format!("setting on line {}", i + 1), line, reason)
+                })?;
+            }
+        }
+
+        Ok(scheme)
+    }
+}
+
+fn rgb(hex: &str) -> Result<Rgb, String> {
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok());
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(format!("malformed shade '#{}', expected #RRGGBB", hex)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn japanese_swaps_blue_and_yellow() {
+        let scheme = Scheme::japanese();
+
+        assert_eq!(scheme.paint(Color::White), Color::White.into());
+        assert_eq!(scheme.paint(Color::Yellow), Paint { letter: 'B', rgb: Color::Blue.rgb() });
+        assert_eq!(scheme.paint(Color::Blue), Paint { letter: 'Y', rgb: Color::Yellow.rgb() });
+        assert_eq!(scheme.find("blue"), Some(Color::Yellow));
+        assert_eq!(scheme.find("Red"), Some(Color::Red));
+    }
+
+    #[test]
+    fn reads_scheme_files() {
+        let scheme: Scheme =
+            "# Stickerless\nU = #FFFFFF\n\nD=blue\nF = #00ff00 L\n".parse().unwrap();
+
+        assert_eq!(scheme.paint(Color::White), Paint { letter: 'W', rgb: [255, 255, 255] });
+        assert_eq!(scheme.paint(Color::Yellow), Color::Blue.into());
+        assert_eq!(scheme.paint(Color::Green), Paint { letter: 'L', rgb: [0, 255, 0] });
+        assert_eq!(scheme.paint(Color::Red), Color::Red.into());
+    }
+
+    #[test]
+    fn reports_bad_settings() {
+        let error = |s: &str| s.parse::<Scheme>().unwrap_err().to_string();
+
+        assert_eq!(error("U white"), "invalid setting on line 1 'U white': expected FACE=COLOR");
+        assert_eq!(
+            error("\nX=red"),
+            "invalid setting on line 2 'X=red': unknown face 'X', expected one of U R F D L B"
+        );
+        assert_eq!(error("U=pink"), "invalid setting on line 1 'U=pink': unknown color 'pink'");
+        assert_eq!(
+            error("U=#fff"),
+            "invalid setting on line 1 'U=#fff': malformed shade '#fff', expected #RRGGBB"
+        );
+        assert_eq!(
+            Scheme::western().apply_setting("U=red R S").unwrap_err().to_string(),
+            "invalid face color 'U=red R S': expected FACE=COLOR"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use super::{stickers, Style};
+use crate::cube::Cube;
+use std::fmt::Write;
+
+/// A standalone SVG document of the net, one square per sticker on a transparent background.
+pub fn svg(cube: &Cube, style: &Style) -> String {
+    let (width, height) = style.size();
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+         viewBox=\"0 0 {0} {1}\">\n",
+        width, height
+    );
+    for sticker in stickers(cube, style) {
+        let [r, g, b] = sticker.color;
+        writeln!(
+            svg,
+            "  <rect x=\"{x}\" y=\"{y}\" width=\"{size}\" height=\"{size}\" \
+             fill=\"#{r:02x}{g:02x}{b:02x}\"/>",
+            x = sticker.x,
+            y = sticker.y,
+            size = style.sticker,
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_every_sticker() {
+        let svg = svg(&Cube::new(), &Style::default());
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"392\""));
+        assert_eq!(svg.matches("<rect ").count(), 54);
+        assert_eq!(svg.matches("fill=\"#efefef\"").count(), 9);
+        assert!(
+            svg.contains("<rect x=\"100\" y=\"2\" width=\"30\" height=\"30\" fill=\"#efefef\"/>")
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use super::{Rgb, Scheme, Style};
+use crate::cube::Color;
+use crate::error::Error;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How tiles are drawn in a terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextMode {
+    /// Blocks in the exact colors of the style.
+    TrueColor,
+    /// Blocks in the nearest of the 256 colors of xterm.
+    Ansi256,
+    /// Blocks in the basic terminal colors, with magenta standing in for orange.
+    Ansi8,
+    /// Initials of the colors, `W G R B O Y`.
+    Letters,
+    /// The face each color belongs on, `U F R B L D`.
+    Faces,
+}
+
+impl TextMode {
+    /// Picks the richest mode the terminal supports, going by the usual environment variables, and
+    /// letters if colors are unwanted (`NO_COLOR`) or the output is not a terminal.
+    pub fn detect() -> TextMode {
+        use std::io::IsTerminal;
+
+        TextMode::from_env(|name| std::env::var(name).ok(), io::stdout().is_terminal())
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> TextMode {
+        let term = var("TERM").unwrap_or_default();
+        if var("NO_COLOR").map_or(false, |v| !v.is_empty()) || !is_terminal || term == "dumb" {
+            TextMode::Letters
+        } else if matches!(var("COLORTERM").as_deref(), Some("truecolor") | Some("24bit")) {
+            TextMode::TrueColor
+        } else if term.contains("256color") {
+            TextMode::Ansi256
+        } else {
+            TextMode::Ansi8
+        }
+    }
+}
+
+/// Accepts `truecolor`, `256`, `8`, `letters` and `faces`, or `auto` to [detect](TextMode::detect)
+/// the mode.
+impl FromStr for TextMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(TextMode::detect()),
+            "truecolor" => Ok(TextMode::TrueColor),
+            "256" => Ok(TextMode::Ansi256),
+            "8" => Ok(TextMode::Ansi8),
+            "letters" => Ok(TextMode::Letters),
+            "faces" => Ok(TextMode::Faces),
+            _ => Err(Error::argument(
+                "render mode",
+                s,
+                "expected one of auto, truecolor, 256, 8, letters, faces",
+            )),
+        }
+    }
+}
+
+/// Writes cubes as text to `out`; see [`Cube::print`](crate::cube::Cube::print).
+pub struct TextRenderer<W: Write> {
+    out: W,
+    mode: TextMode,
+    scheme: Scheme,
+}
+
+impl<W: Write> TextRenderer<W> {
+    /// Writes in `mode`, in the colors of the scheme of `style`.
+    pub fn new(out: W, mode: TextMode, style: &Style) -> Self {
+        TextRenderer { out, mode, scheme: style.scheme.clone() }
+    }
+
+    /// The output written so far.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Writes a tile two characters wide, so that the net keeps its shape in every mode.
+    pub fn write_tile(&mut self, color: Color) -> io::Result<()> {
+        let paint = self.scheme.paint(color);
+        let [r, g, b] = paint.rgb;
+        match self.mode {
+            TextMode::TrueColor => write!(self.out, "\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, BLOCK),
+            TextMode::Ansi256 => {
+                write!(self.out, "\x1b[38;5;{}m{}\x1b[0m", xterm_256([r, g, b]), BLOCK)
+            },
+            TextMode::Ansi8 => write!(self.out, "\x1b[{}m{}\x1b[0m", ansi_8(paint.rgb), BLOCK),
+            TextMode::Letters => write!(self.out, "{} ", paint.letter),
+            TextMode::Faces => write!(self.out, "{} ", color.face_letter()),
+        }
+    }
+}
+
+/// Text other than tiles is passed through.
+impl<W: Write> Write for TextRenderer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+const BLOCK: &str = "\u{2588}\u{2589}";
+
+/// Index of the nearest color in the 6×6×6 cube of xterm's palette.
+fn xterm_256(rgb: Rgb) -> u8 {
+    let [r, g, b] = rgb.map(|c| ((c as u16 * 5 + 127) / 255) as u8);
+    16 + 36 * r + 6 * g + b
+}
+
+/// Foreground code of the usual color nearest to `rgb`. Terminals have no orange, and their yellow
+/// is often brown or orange itself, so orange is shown as magenta.
+fn ansi_8(rgb: Rgb) -> u8 {
+    let distance = |color: &Color| {
+        color.rgb().iter().zip(rgb).map(|(&a, b)| (a as i32 - b as i32).pow(2)).sum::<i32>()
+    };
+    match Color::ALL.into_iter().min_by_key(distance).unwrap() {
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Orange => 35,
+        Color::White => 37,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(mode: TextMode, color: Color) -> String {
+        let mut renderer = TextRenderer::new(vec![], mode, &Style::default());
+        renderer.write_tile(color).unwrap();
+
+        String::from_utf8(renderer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn draws_tiles_in_every_mode() {
+        assert_eq!(tile(TextMode::TrueColor, Color::Red), "\x1b[38;2;178;36;36m█▉\x1b[0m");
+        assert_eq!(tile(TextMode::Ansi256, Color::Red), "\x1b[38;5;131m█▉\x1b[0m");
+        assert_eq!(tile(TextMode::Ansi8, Color::Orange), "\x1b[35m█▉\x1b[0m");
+        assert_eq!(tile(TextMode::Letters, Color::Orange), "O ");
+        assert_eq!(tile(TextMode::Faces, Color::Orange), "L ");
+    }
+
+    #[test]
+    fn follows_scheme() {
+        let style = Style { scheme: Scheme::japanese(), ..Style::default() };
+        let mut renderer = TextRenderer::new(vec![], TextMode::Letters, &style);
+        renderer.write_tile(Color::Yellow).unwrap();
+        renderer.write_tile(Color::Blue).unwrap();
+
+        assert_eq!(String::from_utf8(renderer.into_inner()).unwrap(), "B Y ");
+        assert_eq!(ansi_8(Color::Blue.rgb()), 34);
+        assert_eq!(ansi_8([255, 140, 0]), 35);
+    }
+
+    #[test]
+    fn maps_to_xterm_palette() {
+        assert_eq!(xterm_256([0, 0, 0]), 16);
+        assert_eq!(xterm_256([255, 255, 255]), 231);
+        assert_eq!(xterm_256([255, 102, 0]), 208);
+    }
+
+    #[test]
+    fn detects_mode_from_environment() {
+        let detect = |vars: &[(&str, &str)], is_terminal| {
+            let vars = vars.to_vec();
+            TextMode::from_env(
+                move |name| vars.iter().find(|(n, _)| *n == name).map(|(_, v)| v.to_string()),
+                is_terminal,
+            )
+        };
+
+        assert_eq!(detect(&[("COLORTERM", "truecolor")], true), TextMode::TrueColor);
+        assert_eq!(detect(&[("TERM", "xterm-256color")], true), TextMode::Ansi256);
+        assert_eq!(detect(&[("TERM", "xterm")], true), TextMode::Ansi8);
+        assert_eq!(detect(&[("TERM", "dumb")], true), TextMode::Letters);
+        assert_eq!(detect(&[("COLORTERM", "truecolor")], false), TextMode::Letters);
+        assert_eq!(detect(&[("COLORTERM", "24bit"), ("NO_COLOR", "1")], true), TextMode::Letters);
+        assert_eq!(detect(&[("COLORTERM", "24bit"), ("NO_COLOR", "")], true), TextMode::TrueColor);
+    }
+}
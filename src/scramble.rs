@@ -0,0 +1,114 @@
+//! Scramble generators. Each draws from the given generator only, so a seeded one reproduces its
+//! scrambles exactly.
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::ida;
+use crate::turn::{Successor, Turn};
+use crate::twophase;
+use rand::Rng;
+
+/// `n_turns` random face turns without redundancy: no face is turned twice in a row, and opposite
+/// faces only follow each other in `U D R L F B` order, so neither `R R` nor `R L R` can come up.
+pub fn random_turns<R: Rng + ?Sized>(rng: &mut R, n_turns: u32) -> Vec<Turn> {
+    let mut prev: Option<Turn> = None;
+    (0..n_turns)
+        .map(|_| {
+            let t = match prev {
+                Some(p) => rng.sample(Successor(p)),
+                None => rng.gen(),
+            };
+            prev = Some(t);
+
+            t
+        })
+        .collect()
+}
+
+/// Most random moves [`ida_turns`] accepts. Twelve take about a second; each further move beyond
+/// that makes the search roughly ten times slower.
+pub const IDA_MAX_TURNS: u32 = 12;
+
+/// Scrambles a cube with `n_turns` random moves, then returns the inverse of an optimal solution
+/// found by IDA*, which reaches the same state without wasted moves. The state is only as random
+/// as the moves, unlike those of [`two_phase_turns`]. Fails for more than [`IDA_MAX_TURNS`].
+pub fn ida_turns<R: Rng + ?Sized>(rng: &mut R, n_turns: u32) -> Result<Vec<Turn>, Error> {
+    if n_turns > IDA_MAX_TURNS {
+        let reason = format!("ida searches at most {} moves deep", IDA_MAX_TURNS);
+        return Err(Error::argument("number of moves", &n_turns.to_string(), reason));
+    }
+
+    let state = random_turns(rng, n_turns).into_iter().fold(CubieCube::SOLVED, |c, t| c.mv(t));
+    let solution = ida::solve(&state, n_turns).expect("random state is within its own depth");
+
+    Ok(solution.iter().rev().map(Turn::inverse).collect())
+}
+
+/// Picks a uniformly random state and returns the inverse of a two-phase solution for it, giving
+/// random-state scrambles of at most 21 moves.
+pub fn two_phase_turns<R: Rng + ?Sized>(rng: &mut R) -> Vec<Turn> {
+    let state = CubieCube::random(rng);
+    let solution = twophase::solve(&state, 21).expect("every state is solvable in 20 moves");
+
+    solution.iter().rev().map(Turn::inverse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::Algorithm;
+    use crate::cube::Cube;
+    use crate::random;
+
+    #[test]
+    fn seed_fixes_random_turns() {
+        let turns = random_turns(&mut random::seeded(7), 12);
+
+        assert_eq!(Algorithm::new(turns).to_string(), "D B D' F U B R' L' B2 L' B2 L");
+        assert_eq!(
+            random_turns(&mut random::seeded(7), 25),
+            random_turns(&mut random::seeded(7), 25)
+        );
+    }
+
+    #[test]
+    fn random_turns_do_not_simplify() {
+        for seed in 0..50 {
+            let alg = Algorithm::new(random_turns(&mut random::seeded(seed), 25));
+
+            assert_eq!(alg.simplify(), alg, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn seed_fixes_random_state() {
+        let cube =
+            two_phase_turns(&mut random::seeded(7)).into_iter().fold(Cube::new(), |c, t| c.mv(t));
+
+        assert_eq!(
+            cube.to_facelet_string(),
+            "UFRBULBUDBBBDRFFFLDBLRFFDLLFUDRDLUBFRDRLLUBDRURFUBDURL"
+        );
+    }
+
+    #[test]
+    fn ida_scramble_reaches_same_state() {
+        let state =
+            random_turns(&mut random::seeded(5), 4).into_iter().fold(Cube::new(), |c, t| c.mv(t));
+        let scramble = ida_turns(&mut random::seeded(5), 4).unwrap();
+
+        assert!(scramble.len() <= 4);
+        assert_eq!(
+            scramble.into_iter().fold(Cube::new(), |c, t| c.mv(t)).facelets(),
+            state.facelets()
+        );
+    }
+
+    #[test]
+    fn ida_rejects_deep_scrambles() {
+        assert_eq!(
+            ida_turns(&mut random::seeded(5), 25).unwrap_err().to_string(),
+            "invalid number of moves '25': ida searches at most 12 moves deep"
+        );
+    }
+}
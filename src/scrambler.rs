@@ -1,59 +1,529 @@
-mod cube;
-mod turn;
+use clap::{crate_version, App, AppSettings, Arg, ArgMatches, SubCommand};
+use scrambler::render::{self, Scheme, Sheet, Style, TextMode, TextRenderer};
+use scrambler::{random, scramble, Algorithm, Cube, Error, Metric, Orientation, Result, Turn};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
 
-use crate::cube::Cube;
-use crate::turn::Turn;
-use clap::{crate_version, App, Arg};
-use std::mem::discriminant;
+#[cfg(feature = "serde")]
+use scrambler::json;
 
 fn main() {
+    if let Err(e) = run() {
+        // A closed pipe, e.g. into `head`, means the reader has seen enough.
+        if matches!(&e, Error::Io { path: None, error } if error.kind() == io::ErrorKind::BrokenPipe)
+        {
+            return;
+        }
+        eprintln!("scrambler: {}", e);
+        process::exit(e.exit_code());
+    }
+}
+
+fn run() -> Result<()> {
     let matches = App::new("scrambler")
         .about("Scramble Generator for Rubik's Cube")
         .version(crate_version!())
+        .setting(AppSettings::SubcommandsNegateReqs)
         .arg(
             Arg::with_name("ALGORITHM")
-                .help("Method used to generate scramble")
+                .help(
+                    "Method used to generate scramble: rand and ida give random moves, ida \
+                     shortened to an optimal solution, and two-phase a random state",
+                )
                 .index(1)
-                .possible_values(&["rand", "ida"])
+                .possible_values(&["rand", "ida", "two-phase"])
                 .required(true),
         )
+        .arg(Arg::with_name("num_moves").short("n").value_name("NATURAL").global(true).help(
+            "Number of random moves to generate [default: 25], or to scramble the state solved \
+             by ida, at most 12 [default: 12]",
+        ))
+        .arg(
+            Arg::with_name("count")
+                .short("c")
+                .long("count")
+                .value_name("NATURAL")
+                .default_value("1")
+                .global(true)
+                .help("Number of scrambles to generate"),
+        )
         .arg(
-            Arg::with_name("num_moves")
-                .short("n")
+            Arg::with_name("extras")
+                .short("e")
+                .long("extras")
                 .value_name("NATURAL")
-                .default_value("25")
-                .help("Number of random moves to generate"),
+                .default_value("0")
+                .global(true)
+                .help(
+                    "Number of extra scrambles to generate after the others, numbered E1, E2, ...",
+                ),
+        )
+        .arg(
+            Arg::with_name("seed").long("seed").value_name("NATURAL").global(true).help(
+                "Seed for the random generator; the same seed always gives the same scrambles",
+            ),
+        )
+        .arg(
+            Arg::with_name("metric")
+                .short("m")
+                .long("metric")
+                .value_name("METRIC")
+                .possible_values(&["htm", "qtm", "stm", "etm"])
+                .case_insensitive(true)
+                .global(true)
+                .help("Prints the length of the moves counted in this metric"),
+        )
+        .arg(
+            Arg::with_name("facelets")
+                .short("f")
+                .long("facelets")
+                .global(true)
+                .help("Also prints the state as a 54 character URFDLB facelet string"),
+        )
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .value_name("MODE")
+                .default_value("auto")
+                .possible_values(&["auto", "truecolor", "256", "8", "letters", "faces"])
+                .global(true)
+                .help(
+                    "Draws tiles in true color, 256 or 8 colors, or as color initials or face \
+                     letters; auto picks what the terminal supports and honours NO_COLOR",
+                ),
+        )
+        .arg(Arg::with_name("scheme").long("scheme").value_name("SCHEME").global(true).help(
+            "Colors of the stickers: western, japanese or a scheme file of FACE=COLOR \
+                     lines; defaults to $XDG_CONFIG_HOME/scrambler/scheme if it exists, else \
+                     western",
+        ))
+        .arg(
+            Arg::with_name("face_color")
+                .long("face-color")
+                .value_name("FACE=COLOR")
+                .multiple(true)
+                .number_of_values(1)
+                .global(true)
+                .help(
+                    "Repaints a face of the scheme with a color name or #RRGGBB shade, \
+                     optionally followed by its letter, e.g. \"D=#0000ff B\"",
+                ),
+        )
+        .arg(Arg::with_name("hold").long("hold").value_name("UP-FRONT").global(true).help(
+            "Colors facing up and front while turning, e.g. yellow-red; moves and nets \
+                     follow it, facelet strings stay white up, green front",
+        ))
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("text")
+                .possible_values(if cfg!(feature = "serde") {
+                    &["text", "json"]
+                } else {
+                    &["text"]
+                })
+                .global(true)
+                .help("Prints cubes as text, or as JSON with their moves, facelets and lengths"),
+        )
+        .arg(
+            Arg::with_name("svg")
+                .long("svg")
+                .value_name("PATH")
+                .global(true)
+                .help("Also draws the cube to this SVG file, numbered per scramble"),
+        )
+        .arg(
+            Arg::with_name("png")
+                .long("png")
+                .value_name("PATH")
+                .global(true)
+                .help("Also draws the cube to this PNG file, numbered per scramble"),
+        )
+        .arg(
+            Arg::with_name("sticker_size")
+                .long("sticker-size")
+                .value_name("NATURAL")
+                .default_value("30")
+                .global(true)
+                .help("Width of a sticker in images"),
+        )
+        .arg(
+            Arg::with_name("gap")
+                .long("gap")
+                .value_name("NATURAL")
+                .default_value("2")
+                .global(true)
+                .help("Space between stickers in images"),
+        )
+        .subcommand(
+            SubCommand::with_name("apply")
+                .about("Applies a sequence of moves to a solved cube and shows the result")
+                .arg(
+                    Arg::with_name("MOVES")
+                        .help("Moves in WCA notation, e.g. \"R U R' U'\"")
+                        .index(1)
+                        .multiple(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("simplify")
+                        .short("s")
+                        .long("simplify")
+                        .help("Merges and cancels redundant moves before applying them"),
+                )
+                .arg(
+                    Arg::with_name("pretty").short("p").long("pretty").help(
+                        "Also prints the moves as a commutator or conjugate when they are one",
+                    ),
+                )
+                .arg(
+                    Arg::with_name("invert")
+                        .short("i")
+                        .long("invert")
+                        .help("Applies the inverse of the moves, e.g. to solve a scramble"),
+                )
+                .arg(
+                    Arg::with_name("mirror_lr")
+                        .long("mirror-lr")
+                        .help("Mirrors the moves left to right, through the M slice"),
+                )
+                .arg(
+                    Arg::with_name("mirror_fb")
+                        .long("mirror-fb")
+                        .help("Mirrors the moves front to back, through the S slice"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("sheet")
+                .about("Writes the scrambles to a printable PDF sheet for competitions")
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("PATH")
+                        .required(true)
+                        .help("PDF file to write"),
+                )
+                .arg(
+                    Arg::with_name("event")
+                        .long("event")
+                        .value_name("NAME")
+                        .default_value("3x3x3 Cube")
+                        .help("Event named in the header"),
+                )
+                .arg(
+                    Arg::with_name("round")
+                        .long("round")
+                        .value_name("NATURAL")
+                        .default_value("1")
+                        .help("Round named in the header"),
+                )
+                .arg(
+                    Arg::with_name("group")
+                        .long("group")
+                        .value_name("NAME")
+                        .default_value("A")
+                        .help("Group named in the header"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Shows the state given by a URFDLB facelet string")
+                .arg(
+                    Arg::with_name("FACELETS")
+                        .help("Tiles as 54 URFDLB letters, e.g. \"UUUUUUUUUR...\"")
+                        .index(1)
+                        .required(true),
+                ),
         )
         .get_matches();
 
-    let num_turns: u32 = {
-        let num_str = matches.value_of("num_moves").unwrap();
+    let metric: Option<Metric> = matches.value_of("metric").map(str::parse).transpose()?;
+    let style = Style {
+        sticker: natural(&matches, "sticker_size", "sticker size")?,
+        gap: natural(&matches, "gap", "gap")?,
+        scheme: scheme(&matches)?,
+    };
+    let hold = match matches.value_of("hold") {
+        Some(hold) => Orientation::parse(hold, &style.scheme)?,
+        None => Orientation::default(),
+    };
+    // Cubes are kept white up, green front for facelets and JSON, and turned over for drawing.
+    let turned = |turns: &[Turn]| -> Result<Cube> {
+        let cube = turns.iter().fold(Cube::new().reoriented(hold)?, |c, &t| c.mv(t));
+
+        Ok(cube.reoriented(Orientation::default())?)
+    };
+    let mode: TextMode = matches.value_of("color").unwrap().parse()?;
+    let json = matches.value_of("format") == Some("json");
+    let show = |cube: &Cube, label: Option<&str>| -> Result<()> {
+        let held = cube.reoriented(hold)?;
+        if let Some(path) = matches.value_of("svg") {
+            write_file(&labelled(path, label), render::svg(&held, &style))?;
+        }
+        if let Some(path) = matches.value_of("png") {
+            write_file(&labelled(path, label), render::png(&held, &style)?)?;
+        }
+        if json {
+            return Ok(());
+        }
+
+        let mut renderer = TextRenderer::new(io::stdout().lock(), mode, &style);
+        if let Some(label) = label {
+            writeln!(renderer, "\n{}.", label)?;
+        }
+        held.print(&mut renderer, metric)?;
+        if matches.is_present("facelets") {
+            writeln!(renderer, "\n {}", cube.to_facelet_string())?;
+        }
+
+        Ok(())
+    };
+
+    if let Some(matches) = matches.subcommand_matches("show") {
+        reject(matches, &SCRAMBLE_OPTIONS, "show generates no scrambles")?;
+        reject(matches, &[("metric", "metric")], "show has no moves to count")?;
+        let facelets = matches.value_of("FACELETS").unwrap();
+        let cube = Cube::from_facelet_string(facelets).map_err(|error| Error::Parse {
+            name: "facelets",
+            input: facelets.to_string(),
+            error,
+        })?;
+        cube.validate()?;
+
+        show(&cube, None)?;
+        #[cfg(feature = "serde")]
+        if json {
+            print_json(&json::Entry::new(&cube, None))?;
+        }
 
-        num_str
+        return Ok(());
+    }
+
+    if let Some(matches) = matches.subcommand_matches("apply") {
+        reject(matches, &SCRAMBLE_OPTIONS, "apply generates no scrambles")?;
+        let moves = matches.values_of("MOVES").unwrap().collect::<Vec<_>>().join(" ");
+        let mut alg: Algorithm = moves.parse().map_err(|error| Error::Parse {
+            name: "moves",
+            input: moves.clone(),
+            error,
+        })?;
+        if matches.is_present("simplify") {
+            alg = alg.simplify();
+        }
+        if matches.is_present("invert") {
+            alg = alg.inverse();
+        }
+        if matches.is_present("mirror_lr") {
+            alg = alg.mirror_lr();
+        }
+        if matches.is_present("mirror_fb") {
+            alg = alg.mirror_fb();
+        }
+        if matches.is_present("pretty") && !json {
+            writeln!(io::stdout(), "\n {}", alg.to_notation())?;
+        }
+
+        let cube = turned(alg.turns())?;
+        show(&cube, None)?;
+        #[cfg(feature = "serde")]
+        if json {
+            print_json(&json::Entry::new(&cube, None))?;
+        }
+
+        return Ok(());
+    }
+
+    // Sheets are usually for competitions, so they default to random-state scrambles.
+    let generator = matches.value_of("ALGORITHM").unwrap_or("two-phase");
+    let num_turns = match matches.value_of("num_moves") {
+        Some(_) => natural(&matches, "num_moves", "number of moves")?,
+        None if generator == "ida" => scramble::IDA_MAX_TURNS,
+        None => 25,
+    };
+    let count = natural(&matches, "count", "number of scrambles")?;
+    let extras = natural(&matches, "extras", "number of extras")?;
+    let seed = match matches.value_of("seed") {
+        Some(seed) => seed
             .parse()
-            .unwrap_or_else(|_| panic!("*** Malformed number of moves '{}' ***", num_str))
+            .map_err(|_| Error::argument("seed", seed, "expected a number up to 2^64 - 1"))?,
+        None => rand::random(),
+    };
+    let mut rng = random::seeded(seed);
+    let mut generate = || match generator {
+        "ida" => scramble::ida_turns(&mut rng, num_turns),
+        "two-phase" => Ok(scramble::two_phase_turns(&mut rng)),
+        _ => Ok(scramble::random_turns(&mut rng, num_turns)),
     };
 
-    let mut cube = Cube::new();
-    let turns = generate_random_turns(num_turns);
+    let scrambles = labels(count, extras)
+        .into_iter()
+        .map(|label| Ok((label, turned(&generate()?)?)))
+        .collect::<Result<Vec<(String, Cube)>>>()?;
+
+    if let Some(matches) = matches.subcommand_matches("sheet") {
+        reject(matches, &[("metric", "metric")], "sheets do not show lengths")?;
+        let sheet = Sheet {
+            event: matches.value_of("event").unwrap().to_string(),
+            round: natural(matches, "round", "round")?,
+            group: matches.value_of("group").unwrap().to_string(),
+            scrambles: scrambles
+                .into_iter()
+                .map(|(label, cube)| Ok((label, cube.reoriented(hold)?)))
+                .collect::<Result<_>>()?,
+        };
+
+        return write_file(
+            Path::new(matches.value_of("output").unwrap()),
+            render::pdf(&sheet, &style)?,
+        );
+    }
+
+    let numbered = count + extras > 1;
+    for (label, cube) in &scrambles {
+        show(cube, numbered.then_some(label.as_str()))?;
+    }
+    #[cfg(feature = "serde")]
+    if json {
+        print_json(&json::Scrambles {
+            generator,
+            seed,
+            scrambles: scrambles
+                .iter()
+                .map(|(label, cube)| json::Entry::new(cube, Some(label)))
+                .collect(),
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn print_json(value: &impl serde::Serialize) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).expect("output has string keys only");
+
+    Ok(writeln!(io::stdout(), "{}", json)?)
+}
+
+/// Options only scrambling uses, with how errors name them. They are global so that they can
+/// follow `sheet`, which leaves the other subcommands to [`reject`] them.
+const SCRAMBLE_OPTIONS: [(&str, &str); 4] = [
+    ("num_moves", "number of moves"),
+    ("count", "number of scrambles"),
+    ("extras", "number of extras"),
+    ("seed", "seed"),
+];
 
-    for t in turns {
-        cube = cube.mv(t);
+/// Fails on the first of `options` given on the command line, rather than silently ignoring it.
+fn reject(matches: &ArgMatches, options: &[(&str, &str)], reason: &str) -> Result<()> {
+    for &(name, description) in options {
+        if matches.occurrences_of(name) > 0 {
+            let value = matches.value_of(name).unwrap_or_default();
+            return Err(Error::argument(description, value, reason));
+        }
     }
-    cube.print()
+
+    Ok(())
+}
+
+/// Labels of the scrambles in order: `1`, `2`, ... then `E1`, `E2`, ... for the extras.
+fn labels(count: u32, extras: u32) -> Vec<String> {
+    (1..=count).map(|i| i.to_string()).chain((1..=extras).map(|i| format!("E{}", i))).collect()
+}
+
+/// Appends the scramble's label to a file name, e.g. `scramble.svg` becomes `scramble-E1.svg`.
+fn labelled(path: &str, label: Option<&str>) -> PathBuf {
+    let path = Path::new(path);
+    let label = match label {
+        Some(label) => label,
+        None => return path.to_path_buf(),
+    };
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, label, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, label),
+    })
 }
 
-fn generate_random_turns(n_turns: u32) -> Vec<Turn> {
-    let mut prev: Turn = rand::random();
-    (0..n_turns)
-        .map(|_| {
-            let mut t: Turn = rand::random();
-            while discriminant(&t) == discriminant(&prev) {
-                t = rand::random();
-            }
-            prev = t.clone();
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    fs::write(path, contents).map_err(|error| Error::Io { path: Some(path.into()), error })
+}
+
+/// Reads the scheme named or stored in a file, then applies the face colors given on top of it.
+fn scheme(matches: &ArgMatches) -> Result<Scheme> {
+    let read = |path: &Path| -> Result<Scheme> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| Error::Io { path: Some(path.into()), error })?;
 
-            t
+        text.parse().map_err(|e| match e {
+            Error::Argument { name, value, reason } => {
+                Error::Argument { name: format!("{} of '{}'", name, path.display()), value, reason }
+            },
+            e => e,
         })
-        .collect()
+    };
+    let mut scheme = match matches.value_of("scheme") {
+        Some("western") => Scheme::western(),
+        Some("japanese") => Scheme::japanese(),
+        Some(path) => read(Path::new(path))?,
+        None => match default_scheme_file().filter(|path| path.is_file()) {
+            Some(path) => read(&path)?,
+            None => Scheme::western(),
+        },
+    };
+    for setting in matches.values_of("face_color").into_iter().flatten() {
+        scheme.apply_setting(setting)?;
+    }
+
+    Ok(scheme)
+}
+
+/// `$XDG_CONFIG_HOME/scrambler/scheme`, falling back to `~/.config` as the XDG spec does.
+fn default_scheme_file() -> Option<PathBuf> {
+    let config = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config.join("scrambler").join("scheme"))
+}
+
+/// Reads a numeric option, which must have a value: given, or filled in by clap's default.
+fn natural(matches: &ArgMatches, name: &str, description: &str) -> Result<u32> {
+    let num_str = matches.value_of(name).unwrap();
+
+    num_str.parse().map_err(|_| Error::argument(description, num_str, "expected a natural number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_scrambles_then_extras() {
+        assert_eq!(labels(3, 2), ["1", "2", "3", "E1", "E2"]);
+        assert_eq!(labels(0, 1), ["E1"]);
+        assert!(labels(0, 0).is_empty());
+    }
+
+    #[test]
+    fn labels_file_names() {
+        assert_eq!(labelled("round.png", None), Path::new("round.png"));
+        assert_eq!(labelled("out/round.svg", Some("E1")), Path::new("out/round-E1.svg"));
+        assert_eq!(labelled("round", Some("2")), Path::new("round-2"));
+        assert_eq!(labelled("out/.hidden", Some("1")), Path::new("out/.hidden-1"));
+    }
+
+    #[test]
+    fn numbers_every_image() {
+        let files: Vec<PathBuf> =
+            labels(2, 1).iter().map(|label| labelled("round.png", Some(label))).collect();
+
+        assert_eq!(files, ["round-1.png", "round-2.png", "round-E1.png"].map(PathBuf::from));
+    }
 }
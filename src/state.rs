@@ -0,0 +1,238 @@
+//! The colors of the 54 stickers in a fixed-size array, turned in place through permutation
+//! tables.
+
+use crate::cube::{Color, Face};
+use crate::turn::Turn;
+use std::sync::OnceLock;
+
+/// Every turn, face and slice and rotation alike, by [`index`].
+const N_TURNS: usize = 18 * 3;
+
+/// For each turn, the facelet each facelet's sticker comes from.
+type Permutation = [u8; 54];
+
+/// Sticker colors in `URFDLB` face order, each face read row by row as laid out by
+/// [`Cube::print`](crate::Cube::print). Unlike [`Cube`](crate::Cube) it keeps no move history, so
+/// it is `Copy` and turning it never allocates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct State {
+    facelets: [Color; 54],
+}
+
+impl State {
+    /// A solved cube held white up, green front.
+    pub const SOLVED: State = {
+        let mut facelets = [Color::White; 54];
+        let colors =
+            [Color::White, Color::Red, Color::Green, Color::Yellow, Color::Orange, Color::Blue];
+        let mut i = 0;
+        while i < 54 {
+            facelets[i] = colors[i / 9];
+            i += 1;
+        }
+
+        State { facelets }
+    };
+
+    /// Takes the colors as they are, without checking the state can be reached by turning.
+    pub fn from_facelets(facelets: [Color; 54]) -> Self {
+        State { facelets }
+    }
+
+    /// Every sticker, face after face.
+    pub fn facelets(&self) -> &[Color; 54] {
+        &self.facelets
+    }
+
+    /// Tiles of the face in position `face`, row by row.
+    pub fn face(&self, face: Face) -> &[Color] {
+        let start = face as usize * 9;
+        &self.facelets[start..start + 9]
+    }
+
+    /// Turns the stickers in place.
+    pub fn turn(&mut self, turn: Turn) {
+        let old = self.facelets;
+        for (facelet, &from) in self.facelets.iter_mut().zip(&permutations()[index(turn)]) {
+            *facelet = old[from as usize];
+        }
+    }
+
+    /// Whether every face shows a single color.
+    pub fn is_solved(&self) -> bool {
+        self.misplaced_tiles() == 0
+    }
+
+    /// Number of tiles whose color differs from the center of the face they sit on.
+    pub fn misplaced_tiles(&self) -> usize {
+        self.facelets.chunks(9).map(|face| face.iter().filter(|&&t| t != face[4]).count()).sum()
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::SOLVED
+    }
+}
+
+/// Position of `turn` in the permutation table: three turn types for each layer or rotation, in
+/// the order [`Turn`] declares them.
+fn index(turn: Turn) -> usize {
+    let layer = match turn {
+        Turn::U(_) => 0,
+        Turn::D(_) => 1,
+        Turn::R(_) => 2,
+        Turn::L(_) => 3,
+        Turn::F(_) => 4,
+        Turn::B(_) => 5,
+        Turn::M(_) => 6,
+        Turn::E(_) => 7,
+        Turn::S(_) => 8,
+        Turn::Uw(_) => 9,
+        Turn::Dw(_) => 10,
+        Turn::Rw(_) => 11,
+        Turn::Lw(_) => 12,
+        Turn::Fw(_) => 13,
+        Turn::Bw(_) => 14,
+        Turn::X(_) => 15,
+        Turn::Y(_) => 16,
+        Turn::Z(_) => 17,
+    };
+
+    layer * 3 + turn.turn_type().quarters() as usize - 1
+}
+
+/// The axis a turn goes clockwise around, looking from its tip, and which layers it moves, as the
+/// coordinate of their cubies along that axis: 1 for the face the axis points at, 0 for the slice.
+fn axis_and_layers(layer: usize) -> ([i8; 3], &'static [i8]) {
+    const FACE: &[i8] = &[1];
+    const SLICE: &[i8] = &[0];
+    const WIDE: &[i8] = &[0, 1];
+    const ALL: &[i8] = &[-1, 0, 1];
+
+    match layer {
+        0 => ([0, 1, 0], FACE),
+        1 => ([0, -1, 0], FACE),
+        2 => ([1, 0, 0], FACE),
+        3 => ([-1, 0, 0], FACE),
+        4 => ([0, 0, 1], FACE),
+        5 => ([0, 0, -1], FACE),
+        6 => ([-1, 0, 0], SLICE),
+        7 => ([0, -1, 0], SLICE),
+        8 => ([0, 0, 1], SLICE),
+        9 => ([0, 1, 0], WIDE),
+        10 => ([0, -1, 0], WIDE),
+        11 => ([1, 0, 0], WIDE),
+        12 => ([-1, 0, 0], WIDE),
+        13 => ([0, 0, 1], WIDE),
+        14 => ([0, 0, -1], WIDE),
+        15 => ([1, 0, 0], ALL),
+        16 => ([0, 1, 0], ALL),
+        _ => ([0, 0, 1], ALL),
+    }
+}
+
+/// Where the sticker of a facelet sits, with x pointing right, y up and z to the front. Each
+/// coordinate is twice the cubie's plus the sticker's outward normal, so every sticker has its own
+/// point.
+fn point(facelet: usize) -> [i8; 3] {
+    let (row, column) = ((facelet % 9 / 3) as i8, (facelet % 3) as i8);
+    let (cubie, normal) = match Face::ALL[facelet / 9] {
+        Face::U => ([column - 1, 1, row - 1], [0, 1, 0]),
+        Face::R => ([1, 1 - row, 1 - column], [1, 0, 0]),
+        Face::F => ([column - 1, 1 - row, 1], [0, 0, 1]),
+        Face::D => ([column - 1, -1, 1 - row], [0, -1, 0]),
+        Face::L => ([-1, 1 - row, column - 1], [-1, 0, 0]),
+        Face::B => ([1 - column, 1 - row, -1], [0, 0, -1]),
+    };
+
+    [0, 1, 2].map(|i| 2 * cubie[i] + normal[i])
+}
+
+/// The quarter turn of `layer` clockwise: for each facelet, the facelet its sticker comes from.
+fn quarter_turn(layer: usize) -> Permutation {
+    let (axis, layers) = axis_and_layers(layer);
+    let dot = |v: [i8; 3]| (0..3).map(|i| v[i] * axis[i]).sum::<i8>();
+    let points: Vec<[i8; 3]> = (0..54).map(point).collect();
+
+    let mut permutation = [0; 54];
+    for (from, &p) in points.iter().enumerate() {
+        // Dividing truncates, so stickers on a face layer (±2 or ±3) and in the slice (0) are
+        // told apart.
+        let to = if layers.contains(&(dot(p) / 2)) {
+            // Clockwise about the axis: -(axis × p) + axis (axis · p).
+            let cross = [
+                axis[1] * p[2] - axis[2] * p[1],
+                axis[2] * p[0] - axis[0] * p[2],
+                axis[0] * p[1] - axis[1] * p[0],
+            ];
+            let turned = [0, 1, 2].map(|i| axis[i] * dot(p) - cross[i]);
+            points.iter().position(|&q| q == turned).unwrap()
+        } else {
+            from
+        };
+        permutation[to] = from as u8;
+    }
+
+    permutation
+}
+
+/// Every turn's permutation by [`index`], composed from its layer's quarter turn.
+fn permutations() -> &'static [Permutation; N_TURNS] {
+    static PERMUTATIONS: OnceLock<[Permutation; N_TURNS]> = OnceLock::new();
+    PERMUTATIONS.get_or_init(|| {
+        let mut permutations = [[0; 54]; N_TURNS];
+        for layer in 0..N_TURNS / 3 {
+            // Half and counterclockwise turns are two and three quarter turns.
+            let quarter = quarter_turn(layer);
+            let mut permutation = quarter;
+            for quarters in 0..3 {
+                permutations[layer * 3 + quarters] = permutation;
+                permutation = quarter.map(|from| permutation[from as usize]);
+            }
+        }
+
+        permutations
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::turn::TurnType;
+
+    #[test]
+    fn indexes_every_turn_once() {
+        let mut indices: Vec<usize> = Turn::ALL.into_iter().map(index).collect();
+        indices.sort_unstable();
+
+        assert_eq!(indices, (0..N_TURNS).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn inverse_turn_undoes_turn() {
+        let mut state = State::SOLVED;
+        for turn in "R U2 F' Lw M' B2 y D".parse::<crate::Algorithm>().unwrap().turns() {
+            state.turn(*turn);
+        }
+        for turn in Turn::ALL {
+            let mut turned = state;
+            turned.turn(turn);
+            assert_ne!(turned, state, "{}", turn);
+
+            turned.turn(turn.inverse());
+            assert_eq!(turned, state, "{}", turn);
+        }
+    }
+
+    #[test]
+    fn counts_misplaced_tiles() {
+        let mut state = State::SOLVED;
+        state.turn(Turn::R(TurnType::Clock));
+        assert_eq!(state.misplaced_tiles(), 12);
+
+        state.turn(Turn::X(TurnType::Prime));
+        assert_eq!(state.misplaced_tiles(), 12);
+        assert!(!state.is_solved());
+    }
+}
@@ -1,21 +1,266 @@
+//! Single moves in WCA notation.
+
+use crate::random;
 use rand::{
     distributions::{Distribution, Standard},
     Rng,
 };
+use std::mem::discriminant;
 
-#[derive(Clone)]
+/// A move of a face, slice, pair of layers or the whole cube, as seen from the front.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Turn {
+    /// Up face.
     U(TurnType),
+    /// Down face.
     D(TurnType),
+    /// Right face.
     R(TurnType),
+    /// Left face.
     L(TurnType),
+    /// Front face.
     F(TurnType),
+    /// Back face.
     B(TurnType),
+    /// Middle slice, turning like `L`.
+    M(TurnType),
+    /// Equatorial slice, turning like `D`.
+    E(TurnType),
+    /// Standing slice, turning like `F`.
+    S(TurnType),
+    /// Up face and the slice below it.
+    Uw(TurnType),
+    /// Down face and the slice above it.
+    Dw(TurnType),
+    /// Right face and the slice beside it.
+    Rw(TurnType),
+    /// Left face and the slice beside it.
+    Lw(TurnType),
+    /// Front face and the slice behind it.
+    Fw(TurnType),
+    /// Back face and the slice in front of it.
+    Bw(TurnType),
+    /// Whole cube rotation, turning like `R`.
+    X(TurnType),
+    /// Whole cube rotation, turning like `U`.
+    Y(TurnType),
+    /// Whole cube rotation, turning like `F`.
+    Z(TurnType),
+}
+
+impl Turn {
+    /// Every face turn, grouped by face in `U D R L F B` order.
+    pub const FACE_TURNS: [Turn; 18] = [
+        Turn::U(TurnType::Clock),
+        Turn::U(TurnType::Prime),
+        Turn::U(TurnType::Double),
+        Turn::D(TurnType::Clock),
+        Turn::D(TurnType::Prime),
+        Turn::D(TurnType::Double),
+        Turn::R(TurnType::Clock),
+        Turn::R(TurnType::Prime),
+        Turn::R(TurnType::Double),
+        Turn::L(TurnType::Clock),
+        Turn::L(TurnType::Prime),
+        Turn::L(TurnType::Double),
+        Turn::F(TurnType::Clock),
+        Turn::F(TurnType::Prime),
+        Turn::F(TurnType::Double),
+        Turn::B(TurnType::Clock),
+        Turn::B(TurnType::Prime),
+        Turn::B(TurnType::Double),
+    ];
+
+    /// Every turn there is: each face, slice, pair of layers and rotation in the order declared,
+    /// clockwise, counterclockwise and half.
+    pub const ALL: [Turn; 54] = [
+        Turn::U(TurnType::Clock),
+        Turn::U(TurnType::Prime),
+        Turn::U(TurnType::Double),
+        Turn::D(TurnType::Clock),
+        Turn::D(TurnType::Prime),
+        Turn::D(TurnType::Double),
+        Turn::R(TurnType::Clock),
+        Turn::R(TurnType::Prime),
+        Turn::R(TurnType::Double),
+        Turn::L(TurnType::Clock),
+        Turn::L(TurnType::Prime),
+        Turn::L(TurnType::Double),
+        Turn::F(TurnType::Clock),
+        Turn::F(TurnType::Prime),
+        Turn::F(TurnType::Double),
+        Turn::B(TurnType::Clock),
+        Turn::B(TurnType::Prime),
+        Turn::B(TurnType::Double),
+        Turn::M(TurnType::Clock),
+        Turn::M(TurnType::Prime),
+        Turn::M(TurnType::Double),
+        Turn::E(TurnType::Clock),
+        Turn::E(TurnType::Prime),
+        Turn::E(TurnType::Double),
+        Turn::S(TurnType::Clock),
+        Turn::S(TurnType::Prime),
+        Turn::S(TurnType::Double),
+        Turn::Uw(TurnType::Clock),
+        Turn::Uw(TurnType::Prime),
+        Turn::Uw(TurnType::Double),
+        Turn::Dw(TurnType::Clock),
+        Turn::Dw(TurnType::Prime),
+        Turn::Dw(TurnType::Double),
+        Turn::Rw(TurnType::Clock),
+        Turn::Rw(TurnType::Prime),
+        Turn::Rw(TurnType::Double),
+        Turn::Lw(TurnType::Clock),
+        Turn::Lw(TurnType::Prime),
+        Turn::Lw(TurnType::Double),
+        Turn::Fw(TurnType::Clock),
+        Turn::Fw(TurnType::Prime),
+        Turn::Fw(TurnType::Double),
+        Turn::Bw(TurnType::Clock),
+        Turn::Bw(TurnType::Prime),
+        Turn::Bw(TurnType::Double),
+        Turn::X(TurnType::Clock),
+        Turn::X(TurnType::Prime),
+        Turn::X(TurnType::Double),
+        Turn::Y(TurnType::Clock),
+        Turn::Y(TurnType::Prime),
+        Turn::Y(TurnType::Double),
+        Turn::Z(TurnType::Clock),
+        Turn::Z(TurnType::Prime),
+        Turn::Z(TurnType::Double),
+    ];
+
+    /// Which way and how far the layers go.
+    pub fn turn_type(&self) -> TurnType {
+        match *self {
+            Turn::U(tt)
+            | Turn::D(tt)
+            | Turn::R(tt)
+            | Turn::L(tt)
+            | Turn::F(tt)
+            | Turn::B(tt)
+            | Turn::M(tt)
+            | Turn::E(tt)
+            | Turn::S(tt)
+            | Turn::Uw(tt)
+            | Turn::Dw(tt)
+            | Turn::Rw(tt)
+            | Turn::Lw(tt)
+            | Turn::Fw(tt)
+            | Turn::Bw(tt)
+            | Turn::X(tt)
+            | Turn::Y(tt)
+            | Turn::Z(tt) => tt,
+        }
+    }
+
+    /// The same layer or rotation turned by `turn_type` instead.
+    pub fn with_turn_type(&self, turn_type: TurnType) -> Turn {
+        let layer = match self {
+            Turn::U(_) => Turn::U,
+            Turn::D(_) => Turn::D,
+            Turn::R(_) => Turn::R,
+            Turn::L(_) => Turn::L,
+            Turn::F(_) => Turn::F,
+            Turn::B(_) => Turn::B,
+            Turn::M(_) => Turn::M,
+            Turn::E(_) => Turn::E,
+            Turn::S(_) => Turn::S,
+            Turn::Uw(_) => Turn::Uw,
+            Turn::Dw(_) => Turn::Dw,
+            Turn::Rw(_) => Turn::Rw,
+            Turn::Lw(_) => Turn::Lw,
+            Turn::Fw(_) => Turn::Fw,
+            Turn::Bw(_) => Turn::Bw,
+            Turn::X(_) => Turn::X,
+            Turn::Y(_) => Turn::Y,
+            Turn::Z(_) => Turn::Z,
+        };
+
+        layer(turn_type)
+    }
+
+    /// The turn that undoes this one.
+    pub fn inverse(&self) -> Turn {
+        self.with_turn_type(self.turn_type().inverse())
+    }
+
+    /// The turn seen in a mirror held against the `R` face: `R` and `L` swap and every turn goes
+    /// the other way, except for `M` and `x`, which turn around the mirror's normal.
+    pub fn mirror_lr(&self) -> Turn {
+        match *self {
+            Turn::R(tt) => Turn::L(tt.inverse()),
+            Turn::L(tt) => Turn::R(tt.inverse()),
+            Turn::Rw(tt) => Turn::Lw(tt.inverse()),
+            Turn::Lw(tt) => Turn::Rw(tt.inverse()),
+            Turn::M(_) | Turn::X(_) => *self,
+            _ => self.inverse(),
+        }
+    }
+
+    /// The turn seen in a mirror held against the `F` face, swapping `F` and `B`; `S` and `z` are
+    /// kept.
+    pub fn mirror_fb(&self) -> Turn {
+        match *self {
+            Turn::F(tt) => Turn::B(tt.inverse()),
+            Turn::B(tt) => Turn::F(tt.inverse()),
+            Turn::Fw(tt) => Turn::Bw(tt.inverse()),
+            Turn::Bw(tt) => Turn::Fw(tt.inverse()),
+            Turn::S(_) | Turn::Z(_) => *self,
+            _ => self.inverse(),
+        }
+    }
+
+    /// Whether this turn wastes moves right after `prev`: it turns the same layer, or the opposite
+    /// face out of `U D R L F B` order, so that `U D` is allowed but `D U` and `U D U` are not.
+    /// Slices, wide turns and rotations are only redundant after themselves.
+    pub fn is_redundant_after(&self, prev: &Turn) -> bool {
+        discriminant(self) == discriminant(prev)
+            || (self.same_axis(prev)
+                && matches!((self.face_order(), prev.face_order()), (Some(a), Some(b)) if a < b))
+    }
+
+    /// Position of the face in [`Turn::FACE_TURNS`], or `None` for other turns.
+    fn face_order(&self) -> Option<usize> {
+        Turn::FACE_TURNS.iter().position(|t| discriminant(t) == discriminant(self))
+    }
+
+    /// Whether both turns move layers around the same axis, e.g. `U`, `E` and `y`.
+    pub fn same_axis(&self, other: &Turn) -> bool {
+        self.axis() == other.axis()
+    }
+
+    fn axis(&self) -> usize {
+        match self {
+            Turn::U(_) | Turn::D(_) | Turn::E(_) | Turn::Uw(_) | Turn::Dw(_) | Turn::Y(_) => 0,
+            Turn::R(_) | Turn::L(_) | Turn::M(_) | Turn::Rw(_) | Turn::Lw(_) | Turn::X(_) => 1,
+            Turn::F(_) | Turn::B(_) | Turn::S(_) | Turn::Fw(_) | Turn::Bw(_) | Turn::Z(_) => 2,
+        }
+    }
+
+    /// Slice and wide turns as the equivalent face turns followed by a whole cube rotation. Face
+    /// turns and rotations are returned as they are.
+    pub fn expand(&self) -> Vec<Turn> {
+        let tt = self.turn_type();
+        let inv = tt.inverse();
+        match self {
+            Turn::M(_) => vec![Turn::R(tt), Turn::L(inv), Turn::X(inv)],
+            Turn::E(_) => vec![Turn::U(tt), Turn::D(inv), Turn::Y(inv)],
+            Turn::S(_) => vec![Turn::F(inv), Turn::B(tt), Turn::Z(tt)],
+            Turn::Uw(_) => vec![Turn::D(tt), Turn::Y(tt)],
+            Turn::Dw(_) => vec![Turn::U(tt), Turn::Y(inv)],
+            Turn::Rw(_) => vec![Turn::L(tt), Turn::X(tt)],
+            Turn::Lw(_) => vec![Turn::R(tt), Turn::X(inv)],
+            Turn::Fw(_) => vec![Turn::B(tt), Turn::Z(tt)],
+            Turn::Bw(_) => vec![Turn::F(tt), Turn::Z(inv)],
+            _ => vec![*self],
+        }
+    }
 }
 
 impl Distribution<Turn> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Turn {
-        let r = rng.gen_range(0..18);
+        let r = random::below(rng, 18);
         let turn_type = match r / 6 {
             0 => TurnType::Clock,
             1 => TurnType::Prime,
@@ -33,6 +278,20 @@ impl Distribution<Turn> for Standard {
     }
 }
 
+/// Samples a face turn that may follow the given one, uniformly among those that are not
+/// [redundant](Turn::is_redundant_after) after it.
+#[derive(Clone, Copy, Debug)]
+pub struct Successor(pub Turn);
+
+impl Distribution<Turn> for Successor {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Turn {
+        let legal: Vec<Turn> =
+            Turn::FACE_TURNS.into_iter().filter(|t| !t.is_redundant_after(&self.0)).collect();
+
+        legal[random::below(rng, legal.len() as u32) as usize]
+    }
+}
+
 impl std::fmt::Display for Turn {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -42,17 +301,80 @@ impl std::fmt::Display for Turn {
             Turn::L(tt) => write!(f, "L{}", tt),
             Turn::F(tt) => write!(f, "F{}", tt),
             Turn::B(tt) => write!(f, "B{}", tt),
+            Turn::M(tt) => write!(f, "M{}", tt),
+            Turn::E(tt) => write!(f, "E{}", tt),
+            Turn::S(tt) => write!(f, "S{}", tt),
+            Turn::Uw(tt) => write!(f, "Uw{}", tt),
+            Turn::Dw(tt) => write!(f, "Dw{}", tt),
+            Turn::Rw(tt) => write!(f, "Rw{}", tt),
+            Turn::Lw(tt) => write!(f, "Lw{}", tt),
+            Turn::Fw(tt) => write!(f, "Fw{}", tt),
+            Turn::Bw(tt) => write!(f, "Bw{}", tt),
+            Turn::X(tt) => write!(f, "x{}", tt),
+            Turn::Y(tt) => write!(f, "y{}", tt),
+            Turn::Z(tt) => write!(f, "z{}", tt),
         }
     }
 }
 
-#[derive(Clone)]
+/// Turns are written in WCA notation, e.g. `"Rw'"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Turn {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Turn {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// How far a layer is turned, looking at the face it is named after.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TurnType {
+    /// A quarter turn clockwise, e.g. `R`.
     Clock,
+    /// A quarter turn counterclockwise, e.g. `R'`.
     Prime,
+    /// A half turn, e.g. `R2`.
     Double,
 }
 
+impl TurnType {
+    /// Number of clockwise quarter turns this amounts to.
+    pub fn quarters(&self) -> u8 {
+        match self {
+            TurnType::Clock => 1,
+            TurnType::Double => 2,
+            TurnType::Prime => 3,
+        }
+    }
+
+    /// The turn type amounting to `quarters` clockwise quarter turns, or `None` for a multiple of
+    /// four.
+    pub fn from_quarters(quarters: u8) -> Option<TurnType> {
+        match quarters % 4 {
+            1 => Some(TurnType::Clock),
+            2 => Some(TurnType::Double),
+            3 => Some(TurnType::Prime),
+            _ => None,
+        }
+    }
+
+    /// The turn type going back as far the other way.
+    pub fn inverse(&self) -> TurnType {
+        match self {
+            TurnType::Clock => TurnType::Prime,
+            TurnType::Prime => TurnType::Clock,
+            TurnType::Double => TurnType::Double,
+        }
+    }
+}
+
 impl std::fmt::Display for TurnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -62,3 +384,63 @@ impl std::fmt::Display for TurnType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redundancy_follows_axis_order() {
+        let (u, d, r) =
+            (Turn::U(TurnType::Clock), Turn::D(TurnType::Double), Turn::R(TurnType::Prime));
+
+        assert!(u.is_redundant_after(&u.inverse()));
+        assert!(u.is_redundant_after(&d));
+        assert!(!d.is_redundant_after(&u));
+        assert!(!r.is_redundant_after(&d));
+        assert!(!d.is_redundant_after(&r));
+    }
+
+    #[test]
+    fn only_face_turns_follow_axis_order() {
+        let (r, l) = (Turn::R(TurnType::Clock), Turn::L(TurnType::Clock));
+        for other in [Turn::M(TurnType::Clock), Turn::Rw(TurnType::Clock), Turn::X(TurnType::Prime)]
+        {
+            for face in [r, l] {
+                assert!(!other.is_redundant_after(&face), "{} after {}", other, face);
+                assert!(!face.is_redundant_after(&other), "{} after {}", face, other);
+            }
+            assert!(other.is_redundant_after(&other.inverse()));
+        }
+    }
+
+    #[test]
+    fn successors_are_legal_and_all_reachable() {
+        let mut rng = random::seeded(0);
+        for prev in Turn::FACE_TURNS {
+            let mut seen = vec![];
+            for _ in 0..500 {
+                let next = rng.sample(Successor(prev));
+                assert!(!next.is_redundant_after(&prev), "{} after {}", next, prev);
+                if !seen.contains(&next) {
+                    seen.push(next);
+                }
+            }
+
+            let expected = if prev.face_order().unwrap() / 3 % 2 == 0 { 15 } else { 12 };
+            assert_eq!(seen.len(), expected, "successors of {}", prev);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_notation() {
+        let turns =
+            vec![Turn::R(TurnType::Prime), Turn::Uw(TurnType::Double), Turn::X(TurnType::Clock)];
+        let json = serde_json::to_string(&turns).unwrap();
+
+        assert_eq!(json, r#"["R'","Uw2","x"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Turn>>(&json).unwrap(), turns);
+        assert!(serde_json::from_str::<Turn>(r#""Q""#).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Coordinates packing the parts of a [`CubieCube`] each phase cares about into table indices.
+
+use crate::cubie::CubieCube;
+
+/// Corner orientations, `3^7`.
+pub const N_TWIST: usize = 2187;
+/// Edge orientations, `2^11`.
+pub const N_FLIP: usize = 2048;
+/// Positions of the four UD-slice edges, `12 choose 4`.
+pub const N_SLICE: usize = 495;
+/// Corner permutations, `8!`.
+pub const N_CORNERS: usize = 40320;
+/// Permutations of the eight U and D edges within their layers, `8!`.
+pub const N_UD_EDGES: usize = 40320;
+/// Permutations of the UD-slice edges within the slice, `4!`.
+pub const N_SLICE_SORTED: usize = 24;
+
+/// First UD-slice edge (`FR`); `FR FL BL BR` are edges `8..12`.
+const SLICE_EDGE: u8 = 8;
+
+pub fn twist(cube: &CubieCube) -> usize {
+    cube.co[..7].iter().fold(0, |acc, &co| acc * 3 + co as usize)
+}
+
+pub fn set_twist(cube: &mut CubieCube, mut twist: usize) {
+    let mut sum = 0;
+    for i in (0..7).rev() {
+        cube.co[i] = (twist % 3) as u8;
+        sum += cube.co[i];
+        twist /= 3;
+    }
+    cube.co[7] = (3 - sum % 3) % 3;
+}
+
+pub fn flip(cube: &CubieCube) -> usize {
+    cube.eo[..11].iter().fold(0, |acc, &eo| acc * 2 + eo as usize)
+}
+
+pub fn set_flip(cube: &mut CubieCube, mut flip: usize) {
+    let mut sum = 0;
+    for i in (0..11).rev() {
+        cube.eo[i] = (flip % 2) as u8;
+        sum += cube.eo[i];
+        flip /= 2;
+    }
+    cube.eo[11] = sum % 2;
+}
+
+/// Which four slots hold the UD-slice edges, ignoring their order. Zero when they are all in the
+/// slice.
+pub fn slice(cube: &CubieCube) -> usize {
+    let mut index = 0;
+    let mut seen = 0;
+    for slot in (0..12).rev() {
+        if cube.ep[slot] >= SLICE_EDGE {
+            index += choose(11 - slot, seen + 1);
+            seen += 1;
+        }
+    }
+
+    index
+}
+
+pub fn set_slice(cube: &mut CubieCube, mut slice: usize) {
+    let mut slice_edges = SLICE_EDGE..12;
+    let mut other_edges = 0..SLICE_EDGE;
+    let mut left = 4;
+    for slot in 0..12 {
+        if left > 0 && slice >= choose(11 - slot, left) {
+            slice -= choose(11 - slot, left);
+            left -= 1;
+            cube.ep[slot] = slice_edges.next().unwrap();
+        } else {
+            cube.ep[slot] = other_edges.next().unwrap();
+        }
+    }
+}
+
+pub fn corners(cube: &CubieCube) -> usize {
+    permutation_index(&cube.cp)
+}
+
+pub fn set_corners(cube: &mut CubieCube, corners: usize) {
+    set_permutation(&mut cube.cp, corners, 0);
+}
+
+/// Only meaningful once the UD-slice edges are in the slice.
+pub fn ud_edges(cube: &CubieCube) -> usize {
+    permutation_index(&cube.ep[..8])
+}
+
+pub fn set_ud_edges(cube: &mut CubieCube, ud_edges: usize) {
+    set_permutation(&mut cube.ep[..8], ud_edges, 0);
+    set_permutation(&mut cube.ep[8..], 0, SLICE_EDGE);
+}
+
+/// Only meaningful once the UD-slice edges are in the slice.
+pub fn slice_sorted(cube: &CubieCube) -> usize {
+    permutation_index(&cube.ep[8..])
+}
+
+pub fn set_slice_sorted(cube: &mut CubieCube, slice_sorted: usize) {
+    set_permutation(&mut cube.ep[..8], 0, 0);
+    set_permutation(&mut cube.ep[8..], slice_sorted, SLICE_EDGE);
+}
+
+/// Lehmer code of a permutation of distinct values.
+fn permutation_index(perm: &[u8]) -> usize {
+    (0..perm.len()).fold(0, |acc, i| {
+        let smaller_after = perm[i + 1..].iter().filter(|&&p| p < perm[i]).count();
+        acc * (perm.len() - i) + smaller_after
+    })
+}
+
+/// Fills `perm` with the permutation of `offset..offset + perm.len()` having Lehmer code `index`.
+fn set_permutation(perm: &mut [u8], mut index: usize, offset: u8) {
+    let n = perm.len();
+    let mut digits = vec![0; n];
+    for i in (0..n).rev() {
+        digits[i] = index % (n - i);
+        index /= n - i;
+    }
+
+    let mut unused: Vec<u8> = (offset..offset + n as u8).collect();
+    for (slot, digit) in perm.iter_mut().zip(digits) {
+        *slot = unused.remove(digit);
+    }
+}
+
+fn choose(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solved_cube_is_all_zero() {
+        let cube = CubieCube::SOLVED;
+
+        assert_eq!(
+            [twist(&cube), flip(&cube), slice(&cube), corners(&cube), ud_edges(&cube)],
+            [0; 5]
+        );
+        assert_eq!(slice_sorted(&cube), 0);
+    }
+
+    #[test]
+    fn setters_round_trip() {
+        let mut cube = CubieCube::SOLVED;
+        for i in (0..N_TWIST).step_by(7) {
+            set_twist(&mut cube, i);
+            assert_eq!(twist(&cube), i);
+        }
+        for i in (0..N_FLIP).step_by(7) {
+            set_flip(&mut cube, i);
+            assert_eq!(flip(&cube), i);
+        }
+        for i in 0..N_SLICE {
+            set_slice(&mut cube, i);
+            assert_eq!(slice(&cube), i);
+        }
+        for i in (0..N_CORNERS).step_by(97) {
+            set_corners(&mut cube, i);
+            assert_eq!(corners(&cube), i);
+        }
+        for i in (0..N_UD_EDGES).step_by(97) {
+            set_ud_edges(&mut cube, i);
+            assert_eq!(ud_edges(&cube), i);
+        }
+        for i in 0..N_SLICE_SORTED {
+            set_slice_sorted(&mut cube, i);
+            assert_eq!(slice_sorted(&cube), i);
+        }
+    }
+}
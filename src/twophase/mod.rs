@@ -0,0 +1,156 @@
+//! Kociemba's two-phase algorithm.
+//!
+//! Phase 1 brings the cube into the subgroup G1 = <U, D, R2, L2, F2, B2>, where every corner and
+//! edge is oriented and the UD-slice edges sit in the slice. Phase 2 then solves the cube using
+//! only G1 moves. Both phases are IDA* searches over coordinates, guided by pruning tables.
+
+pub(crate) mod coord;
+pub(crate) mod tables;
+
+use crate::cubie::CubieCube;
+use crate::turn::Turn;
+use tables::{tables, Tables};
+
+/// Turns indexed by move number, as used by the move tables.
+const TURNS: [Turn; 18] = Turn::FACE_TURNS;
+
+/// `U U' U2 D D' D2 R2 L2 F2 B2`.
+const PHASE2_MOVES: [usize; 10] = [0, 1, 2, 3, 4, 5, 8, 11, 14, 17];
+
+/// Finds a sequence of at most `max_length` face turns that solves `cube`.
+///
+/// Phase 1 solutions are tried in order of length, each followed by the shortest phase 2 that fits
+/// in the remaining budget, so the first solution found is returned rather than an optimal one.
+/// Solutions of up to 21 turns are found quickly; much shorter limits can take a long time.
+pub fn solve(cube: &CubieCube, max_length: usize) -> Option<Vec<Turn>> {
+    let mut search = Search { tables: tables(), cube: *cube, path: vec![], max_length };
+    let twist = coord::twist(cube);
+    let flip = coord::flip(cube);
+    let slice = coord::slice(cube);
+
+    (0..=max_length)
+        .find(|&depth| search.phase1(twist, flip, slice, depth))
+        .map(|_| search.path.iter().map(|&m| TURNS[m]).collect())
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    cube: CubieCube,
+    path: Vec<usize>,
+    max_length: usize,
+}
+
+impl Search<'_> {
+    fn phase1(&mut self, twist: usize, flip: usize, slice: usize, togo: usize) -> bool {
+        let t = self.tables;
+        let dist = t.twist_slice_prune[twist * coord::N_SLICE + slice]
+            .max(t.flip_slice_prune[flip * coord::N_SLICE + slice]) as usize;
+        if dist > togo {
+            return false;
+        }
+        if togo == 0 {
+            // Ending phase 1 on a G1 move means a shorter phase 1 was already tried.
+            let ends_in_g1 = self.path.last().map_or(false, |m| PHASE2_MOVES.contains(m));
+            return !ends_in_g1 && self.start_phase2();
+        }
+
+        for m in 0..18 {
+            if self.path.last().map_or(false, |&prev| redundant(prev, m)) {
+                continue;
+            }
+
+            self.path.push(m);
+            let found = self.phase1(
+                t.twist_move[twist][m] as usize,
+                t.flip_move[flip][m] as usize,
+                t.slice_move[slice][m] as usize,
+                togo - 1,
+            );
+            if found {
+                return true;
+            }
+            self.path.pop();
+        }
+
+        false
+    }
+
+    fn start_phase2(&mut self) -> bool {
+        let cube = self.path.iter().fold(self.cube, |c, &m| c.mv(TURNS[m]));
+        let corners = coord::corners(&cube);
+        let ud_edges = coord::ud_edges(&cube);
+        let slice_sorted = coord::slice_sorted(&cube);
+
+        let budget = self.max_length - self.path.len();
+        (0..=budget).any(|depth| self.phase2(corners, ud_edges, slice_sorted, depth))
+    }
+
+    fn phase2(
+        &mut self,
+        corners: usize,
+        ud_edges: usize,
+        slice_sorted: usize,
+        togo: usize,
+    ) -> bool {
+        let t = self.tables;
+        let dist = t.corners_slice_prune[corners * coord::N_SLICE_SORTED + slice_sorted]
+            .max(t.ud_edges_slice_prune[ud_edges * coord::N_SLICE_SORTED + slice_sorted])
+            as usize;
+        if dist > togo {
+            return false;
+        }
+        if togo == 0 {
+            return true;
+        }
+
+        for m in PHASE2_MOVES {
+            if self.path.last().map_or(false, |&prev| redundant(prev, m)) {
+                continue;
+            }
+
+            self.path.push(m);
+            let found = self.phase2(
+                t.corners_move[corners][m] as usize,
+                t.ud_edges_move[ud_edges][m] as usize,
+                t.slice_sorted_move[slice_sorted][m] as usize,
+                togo - 1,
+            );
+            if found {
+                return true;
+            }
+            self.path.pop();
+        }
+
+        false
+    }
+}
+
+/// Same face twice in a row, or opposite faces out of `U D R L F B` order.
+fn redundant(prev: usize, next: usize) -> bool {
+    let (prev_face, next_face) = (prev / 3, next / 3);
+
+    prev_face == next_face || (prev_face / 2 == next_face / 2 && next_face < prev_face)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random;
+
+    #[test]
+    fn solves_random_states() {
+        let mut rng = random::seeded(3);
+        for _ in 0..5 {
+            let cube = CubieCube::random(&mut rng);
+            let solution = solve(&cube, 21).unwrap();
+
+            assert!(solution.len() <= 21);
+            assert_eq!(solution.into_iter().fold(cube, |c, t| c.mv(t)), CubieCube::SOLVED);
+        }
+    }
+
+    #[test]
+    fn solved_cube_needs_no_turns() {
+        assert_eq!(solve(&CubieCube::SOLVED, 21), Some(vec![]));
+    }
+}
@@ -0,0 +1,117 @@
+//! Move tables, which step a coordinate through each turn, and pruning tables, which hold the exact
+//! number of turns needed to zero a pair of coordinates.
+
+use super::coord::{self, *};
+use super::{PHASE2_MOVES, TURNS};
+use crate::cubie::CubieCube;
+use std::sync::OnceLock;
+
+/// Marks pruning table entries not yet reached by the breadth-first search.
+const UNVISITED: u8 = u8::MAX;
+
+pub struct Tables {
+    pub twist_move: Vec<[u16; 18]>,
+    pub flip_move: Vec<[u16; 18]>,
+    pub slice_move: Vec<[u16; 18]>,
+    pub corners_move: Vec<[u16; 18]>,
+    /// Only filled in for phase 2 moves.
+    pub ud_edges_move: Vec<[u16; 18]>,
+    /// Only filled in for phase 2 moves.
+    pub slice_sorted_move: Vec<[u16; 18]>,
+    /// Indexed by `twist * N_SLICE + slice`.
+    pub twist_slice_prune: Vec<u8>,
+    /// Indexed by `flip * N_SLICE + slice`.
+    pub flip_slice_prune: Vec<u8>,
+    /// Indexed by `corners * N_SLICE_SORTED + slice_sorted`.
+    pub corners_slice_prune: Vec<u8>,
+    /// Indexed by `ud_edges * N_SLICE_SORTED + slice_sorted`.
+    pub ud_edges_slice_prune: Vec<u8>,
+}
+
+/// The tables are generated on first use, which takes a moment, and shared afterwards.
+pub fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(Tables::generate)
+}
+
+impl Tables {
+    fn generate() -> Self {
+        let all_moves: Vec<usize> = (0..18).collect();
+
+        let twist_move = move_table(N_TWIST, coord::set_twist, coord::twist, &all_moves);
+        let flip_move = move_table(N_FLIP, coord::set_flip, coord::flip, &all_moves);
+        let slice_move = move_table(N_SLICE, coord::set_slice, coord::slice, &all_moves);
+        let corners_move = move_table(N_CORNERS, coord::set_corners, coord::corners, &all_moves);
+        let ud_edges_move =
+            move_table(N_UD_EDGES, coord::set_ud_edges, coord::ud_edges, &PHASE2_MOVES);
+        let slice_sorted_move =
+            move_table(N_SLICE_SORTED, coord::set_slice_sorted, coord::slice_sorted, &PHASE2_MOVES);
+
+        let twist_slice_prune = pruning_table(&twist_move, &slice_move, &all_moves);
+        let flip_slice_prune = pruning_table(&flip_move, &slice_move, &all_moves);
+        let corners_slice_prune = pruning_table(&corners_move, &slice_sorted_move, &PHASE2_MOVES);
+        let ud_edges_slice_prune = pruning_table(&ud_edges_move, &slice_sorted_move, &PHASE2_MOVES);
+
+        Tables {
+            twist_move,
+            flip_move,
+            slice_move,
+            corners_move,
+            ud_edges_move,
+            slice_sorted_move,
+            twist_slice_prune,
+            flip_slice_prune,
+            corners_slice_prune,
+            ud_edges_slice_prune,
+        }
+    }
+}
+
+fn move_table(
+    size: usize,
+    set: fn(&mut CubieCube, usize),
+    get: fn(&CubieCube) -> usize,
+    moves: &[usize],
+) -> Vec<[u16; 18]> {
+    let turn_cubes = TURNS.map(|t| CubieCube::SOLVED.mv(t));
+
+    (0..size)
+        .map(|i| {
+            let mut cube = CubieCube::SOLVED;
+            set(&mut cube, i);
+
+            let mut row = [0; 18];
+            for &m in moves {
+                row[m] = get(&cube.multiply(&turn_cubes[m])) as u16;
+            }
+
+            row
+        })
+        .collect()
+}
+
+/// Breadth-first search from the solved pair `(0, 0)` over `moves`.
+fn pruning_table(outer: &[[u16; 18]], inner: &[[u16; 18]], moves: &[usize]) -> Vec<u8> {
+    let mut table = vec![UNVISITED; outer.len() * inner.len()];
+    table[0] = 0;
+
+    let mut frontier = vec![0];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        let mut next_frontier = vec![];
+        for index in frontier {
+            let (o, i) = (index / inner.len(), index % inner.len());
+            for &m in moves {
+                let next = outer[o][m] as usize * inner.len() + inner[i][m] as usize;
+                if table[next] == UNVISITED {
+                    table[next] = depth;
+                    next_frontier.push(next);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    table
+}